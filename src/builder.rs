@@ -0,0 +1,412 @@
+use crate::store::{FileTokenStore, TokenStore};
+use crate::token::AuthorizeCallback;
+use crate::{secs, GrantType, Session, SessionError};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use url::Url;
+
+/// Settings of the HTTP client shared by every request of a `Session`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+pub struct HttpConfig {
+    #[serde(rename = "timeout_secs", default, deserialize_with = "secs")]
+    pub timeout: Option<Duration>,
+    #[serde(rename = "connect_timeout_secs", default, deserialize_with = "secs")]
+    pub connect_timeout: Option<Duration>,
+    /// Defaults to `ftapi/<version>`.
+    pub user_agent: Option<String>,
+    /// Proxy url used for every request.
+    pub proxy: Option<String>,
+    pub pool_max_idle_per_host: Option<usize>,
+}
+
+impl HttpConfig {
+    /// Builds the client shared by API and OAuth requests.
+    ///
+    /// Redirects are never followed: OAuth requests carry the client secret,
+    /// codes and refresh tokens in their body, which a redirect could hand to
+    /// another host.
+    pub fn build_client(&self) -> Result<reqwest::Client, SessionError> {
+        let user_agent = match &self.user_agent {
+            Some(user_agent) => user_agent.clone(),
+            None => format!("ftapi/{}", env!("CARGO_PKG_VERSION")),
+        };
+        let mut builder = reqwest::Client::builder()
+            .user_agent(user_agent)
+            .redirect(reqwest::redirect::Policy::none());
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(reqwest::Proxy::all(proxy.as_str())?);
+        }
+        if let Some(max) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max);
+        }
+        Ok(builder.build()?)
+    }
+}
+
+impl HttpConfig {
+    /// Overrides the settings that are set in `other`.
+    fn merge(&mut self, other: HttpConfig) {
+        if other.timeout.is_some() {
+            self.timeout = other.timeout;
+        }
+        if other.connect_timeout.is_some() {
+            self.connect_timeout = other.connect_timeout;
+        }
+        if other.user_agent.is_some() {
+            self.user_agent = other.user_agent;
+        }
+        if other.proxy.is_some() {
+            self.proxy = other.proxy;
+        }
+        if other.pool_max_idle_per_host.is_some() {
+            self.pool_max_idle_per_host = other.pool_max_idle_per_host;
+        }
+    }
+}
+
+/// Environment variables read by [`SessionBuilder::env`].
+pub const ENV_CLIENT_ID: &str = "FT_CLIENT_ID";
+pub const ENV_CLIENT_SECRET: &str = "FT_CLIENT_SECRET";
+pub const ENV_ACCESS_TOKEN: &str = "FT_ACCESS_TOKEN";
+pub const ENV_REFRESH_TOKEN: &str = "FT_REFRESH_TOKEN";
+pub const ENV_BASE_URL: &str = "FT_BASE_URL";
+/// Scopes separated by spaces or commas.
+pub const ENV_SCOPES: &str = "FT_SCOPES";
+pub const ENV_TOKEN_FILE: &str = "FT_TOKEN_FILE";
+
+/// Builds a `Session` together with the HTTP client it reuses for all requests.
+///
+/// Configuration is layered: the TOML file first, then the `FT_*` environment
+/// variables, then the values given to the setters, whatever the call order.
+#[derive(Debug, Default)]
+pub struct SessionBuilder {
+    session: Option<Session>,
+    config_file: Option<PathBuf>,
+    env: bool,
+    client_id: Option<String>,
+    client_secret: Option<String>,
+    access_token: Option<String>,
+    base_url: Option<String>,
+    grant_type: Option<GrantType>,
+    scopes: Option<Vec<String>>,
+    use_pkce: Option<bool>,
+    token_store: Option<Arc<dyn TokenStore>>,
+    interactive: Option<bool>,
+    authorize_callback: Option<AuthorizeCallback>,
+    http: HttpConfig,
+    client: Option<reqwest::Client>,
+}
+
+impl SessionBuilder {
+    pub fn new() -> Self {
+        SessionBuilder::default()
+    }
+    /// Starts from an already configured session instead of the defaults.
+    pub fn from_session(session: Session) -> Self {
+        SessionBuilder {
+            session: Some(session),
+            ..SessionBuilder::default()
+        }
+    }
+    /// Reads the TOML file at `path`, it replaces the `from_session` base.
+    pub fn config_file<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.config_file = Some(path.as_ref().to_path_buf());
+        self
+    }
+    /// Reads the `FT_*` environment variables.
+    pub fn env(mut self) -> Self {
+        self.env = true;
+        self
+    }
+    pub fn client_id<S: Into<String>>(mut self, client_id: S) -> Self {
+        self.client_id = Some(client_id.into());
+        self
+    }
+    pub fn client_secret<S: Into<String>>(mut self, client_secret: S) -> Self {
+        self.client_secret = Some(client_secret.into());
+        self
+    }
+    pub fn access_token<S: Into<String>>(mut self, access_token: S) -> Self {
+        self.access_token = Some(access_token.into());
+        self
+    }
+    pub fn base_url<S: Into<String>>(mut self, base_url: S) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+    pub fn grant_type(mut self, grant_type: GrantType) -> Self {
+        self.grant_type = Some(grant_type);
+        self
+    }
+    pub fn scopes<I, S>(mut self, scopes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.scopes = Some(scopes.into_iter().map(Into::into).collect());
+        self
+    }
+    pub fn token_store(mut self, store: Arc<dyn TokenStore>) -> Self {
+        self.token_store = Some(store);
+        self
+    }
+    /// Public clients authenticate with PKCE and need no client secret.
+    pub fn use_pkce(mut self, use_pkce: bool) -> Self {
+        self.use_pkce = Some(use_pkce);
+        self
+    }
+    /// See `Session::set_interactive`.
+    pub fn interactive(mut self, interactive: bool) -> Self {
+        self.interactive = Some(interactive);
+        self
+    }
+    /// See `Session::set_authorize_callback`.
+    pub fn on_authorize<F: Fn(&Url) + Send + Sync + 'static>(mut self, callback: F) -> Self {
+        self.authorize_callback = Some(AuthorizeCallback::new(callback));
+        self
+    }
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.http.timeout = Some(timeout);
+        self
+    }
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.http.connect_timeout = Some(timeout);
+        self
+    }
+    pub fn user_agent<S: Into<String>>(mut self, user_agent: S) -> Self {
+        self.http.user_agent = Some(user_agent.into());
+        self
+    }
+    pub fn proxy<S: Into<String>>(mut self, proxy: S) -> Self {
+        self.http.proxy = Some(proxy.into());
+        self
+    }
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.http.pool_max_idle_per_host = Some(max);
+        self
+    }
+    /// Uses `client` as is, e.g. for a custom TLS setup. The http settings are ignored.
+    ///
+    /// It also sends the OAuth requests, so it should not follow redirects.
+    pub fn http_client(mut self, client: reqwest::Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+    pub fn build(self) -> Result<Session, SessionError> {
+        let lookup = |name: &str| std::env::var(name).ok();
+        self.build_with_env(lookup)
+    }
+
+    fn build_with_env<F>(self, lookup: F) -> Result<Session, SessionError>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let mut session = match &self.config_file {
+            Some(path) => toml::from_str(&fs::read_to_string(path)?)?,
+            None => self.session.unwrap_or_default(),
+        };
+        if self.env {
+            apply_env(&mut session, lookup);
+        }
+        if let Some(client_id) = self.client_id {
+            session.client_id = client_id;
+        }
+        if let Some(client_secret) = self.client_secret {
+            session.client_secret = client_secret;
+        }
+        if self.access_token.is_some() {
+            session.access_token = self.access_token;
+        }
+        if let Some(base_url) = self.base_url {
+            session.base_url = base_url;
+        }
+        if let Some(grant_type) = self.grant_type {
+            session.grant_type = grant_type;
+        }
+        if let Some(scopes) = self.scopes {
+            session.scopes = scopes;
+        }
+        if let Some(use_pkce) = self.use_pkce {
+            session.use_pkce = use_pkce;
+        }
+        if self.token_store.is_some() {
+            session.token_store = self.token_store;
+        }
+        if let Some(interactive) = self.interactive {
+            session.interactive = interactive;
+        }
+        if self.authorize_callback.is_some() {
+            session.authorize_callback = self.authorize_callback;
+        }
+        session.http.merge(self.http);
+
+        let sources = |field: &str, env_name: &str| {
+            let mut sources = Vec::new();
+            if let Some(path) = &self.config_file {
+                sources.push(format!("`{}` in {}", field, path.display()));
+            }
+            if self.env {
+                sources.push(format!("environment variable {}", env_name));
+            }
+            sources.push(format!("SessionBuilder::{}", field));
+            sources.join(", ")
+        };
+        if session.client_id.is_empty() {
+            return Err(SessionError::MissingConfig {
+                field: "client_id",
+                sources: sources("client_id", ENV_CLIENT_ID),
+            });
+        }
+        // Public clients using PKCE have no secret.
+        if session.client_secret.is_empty() && !session.use_pkce {
+            return Err(SessionError::MissingConfig {
+                field: "client_secret",
+                sources: format!(
+                    "{}, or enable SessionBuilder::use_pkce",
+                    sources("client_secret", ENV_CLIENT_SECRET)
+                ),
+            });
+        }
+
+        session.client = match self.client {
+            Some(client) => client,
+            None => session.http.build_client()?,
+        };
+        Ok(session)
+    }
+}
+
+fn apply_env<F: Fn(&str) -> Option<String>>(session: &mut Session, lookup: F) {
+    if let Some(client_id) = lookup(ENV_CLIENT_ID) {
+        session.client_id = client_id;
+    }
+    if let Some(client_secret) = lookup(ENV_CLIENT_SECRET) {
+        session.client_secret = client_secret;
+    }
+    if let Some(access_token) = lookup(ENV_ACCESS_TOKEN) {
+        session.access_token = Some(access_token);
+    }
+    if let Some(refresh_token) = lookup(ENV_REFRESH_TOKEN) {
+        session.refresh_token = Some(refresh_token);
+    }
+    if let Some(base_url) = lookup(ENV_BASE_URL) {
+        session.base_url = base_url;
+    }
+    if let Some(scopes) = lookup(ENV_SCOPES) {
+        session.scopes = scopes
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|scope| !scope.is_empty())
+            .map(str::to_string)
+            .collect();
+    }
+    if let Some(path) = lookup(ENV_TOKEN_FILE) {
+        session.token_store = Some(Arc::new(FileTokenStore::new(path)));
+    }
+}
+
+#[test]
+fn session_builder_http_test() {
+    let session = SessionBuilder::new()
+        .client_id("id")
+        .client_secret("secret")
+        .timeout(Duration::from_secs(10))
+        .user_agent("campus-bot/1.0")
+        .pool_max_idle_per_host(4)
+        .build()
+        .unwrap();
+    assert_eq!(
+        session.get_http_config().timeout,
+        Some(Duration::from_secs(10))
+    );
+    assert_eq!(
+        session.get_http_config().user_agent.as_deref(),
+        Some("campus-bot/1.0")
+    );
+    assert!(SessionBuilder::new()
+        .client_id("id")
+        .client_secret("secret")
+        .proxy("not a proxy")
+        .build()
+        .is_err());
+}
+
+#[test]
+fn http_config_toml_test() {
+    let session: Session = toml::from_str(
+        r#"
+        client_id = "id"
+        client_secret = "secret"
+
+        [http]
+        timeout_secs = 30
+        proxy = "http://proxy.local:3128"
+        "#,
+    )
+    .unwrap();
+    let http = session.get_http_config();
+    assert_eq!(http.timeout, Some(Duration::from_secs(30)));
+    assert_eq!(http.proxy.as_deref(), Some("http://proxy.local:3128"));
+    assert!(SessionBuilder::from_session(session).build().is_ok());
+}
+
+#[test]
+fn session_builder_layers_test() {
+    use std::collections::HashMap;
+
+    let path = std::env::temp_dir().join(format!("ftapi-config-{}.toml", std::process::id()));
+    fs::write(
+        &path,
+        "client_id = \"file-id\"\nclient_secret = \"file-secret\"\nbase_url = \"http://file\"\n",
+    )
+    .unwrap();
+    let env: HashMap<&str, &str> = [
+        (ENV_CLIENT_SECRET, "env-secret"),
+        (ENV_SCOPES, "public, projects"),
+    ]
+    .into_iter()
+    .collect();
+    let lookup = |name: &str| env.get(name).map(|v| v.to_string());
+
+    let session = SessionBuilder::new()
+        .base_url("http://builder")
+        .config_file(&path)
+        .env()
+        .build_with_env(lookup)
+        .unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(session.get_client_id(), "file-id");
+    assert_eq!(session.get_client_secret(), "env-secret");
+    assert_eq!(session.get_scopes(), ["public", "projects"]);
+    assert_eq!(session.get_base_url(), "http://builder");
+}
+
+#[test]
+fn session_builder_missing_config_test() {
+    let res = SessionBuilder::new()
+        .client_id("id")
+        .env()
+        .build_with_env(|_| None);
+    match res {
+        Err(SessionError::MissingConfig { field, sources }) => {
+            assert_eq!(field, "client_secret");
+            assert!(sources.contains(ENV_CLIENT_SECRET));
+            assert!(sources.contains("use_pkce"));
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+    let session = SessionBuilder::new()
+        .client_id("id")
+        .use_pkce(true)
+        .build_with_env(|_| None)
+        .unwrap();
+    assert!(session.get_client_secret().is_empty());
+}
@@ -1,18 +1,138 @@
+pub mod builder;
+pub mod paginate;
+pub mod ratelimit;
 pub mod results;
+pub mod retry;
+pub mod store;
 pub mod token;
 
-use crate::token::{check_token_valide, generate_token};
+use crate::builder::{HttpConfig, SessionBuilder};
+use crate::ratelimit::{RateLimit, RateLimiter};
+use crate::results::campus::Campus;
+use crate::results::me::Me;
+use crate::retry::RetryPolicy;
+use crate::store::{unix_now, StoredToken, TokenStore};
+use crate::token::{generate_token, refresh_token, token_info, AuthorizeCallback};
 use log::{self, debug, warn};
-use reqwest::header::AUTHORIZATION;
+use reqwest::header::{HeaderMap, AUTHORIZATION, RETRY_AFTER};
+use reqwest::{Method, StatusCode};
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
-use std::fs;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
 use thiserror::Error;
 
-#[derive(Clone, Debug, Default, Deserialize)]
+/// Default root of the 42 API. Every endpoint path is resolved against it.
+pub const DEFAULT_BASE_URL: &str = "https://api.intra.42.fr";
+
+/// A token expiring sooner than this is checked or refreshed before use.
+pub const TOKEN_EXPIRY_MARGIN: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Debug, Deserialize)]
 pub struct Session {
+    #[serde(default)]
     client_id: String,
+    #[serde(default)]
     client_secret: String,
     access_token: Option<String>,
+    refresh_token: Option<String>,
+    #[serde(skip)]
+    token_expires_at: Option<i64>,
+    #[serde(
+        default,
+        rename = "token_file",
+        deserialize_with = "store::deserialize_token_file"
+    )]
+    token_store: Option<Arc<dyn TokenStore>>,
+    #[serde(default = "default_base_url")]
+    base_url: String,
+    auth_url: Option<String>,
+    token_url: Option<String>,
+    token_info_url: Option<String>,
+    #[serde(default)]
+    grant_type: GrantType,
+    #[serde(default = "default_scopes")]
+    scopes: Vec<String>,
+    #[serde(default)]
+    use_pkce: bool,
+    #[serde(default = "default_interactive")]
+    interactive: bool,
+    #[serde(skip)]
+    authorize_callback: Option<AuthorizeCallback>,
+    #[serde(default = "default_redirect_url")]
+    redirect_url: String,
+    bind_address: Option<String>,
+    #[serde(rename = "login_timeout_secs", default, deserialize_with = "secs")]
+    login_timeout: Option<Duration>,
+    #[serde(default, rename = "rate_limit")]
+    rate_limiter: RateLimiter,
+    #[serde(default, rename = "retry")]
+    retry_policy: RetryPolicy,
+    #[serde(default)]
+    http: HttpConfig,
+    #[serde(skip)]
+    client: reqwest::Client,
+}
+
+/// OAuth2 grant used when the session needs a new access token.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GrantType {
+    /// Browser based login, redirected back to a local server.
+    #[default]
+    AuthorizationCode,
+    /// Application token obtained without user interaction.
+    ClientCredentials,
+}
+
+fn default_scopes() -> Vec<String> {
+    vec!["public".to_string()]
+}
+
+fn default_interactive() -> bool {
+    true
+}
+
+fn default_redirect_url() -> String {
+    "http://localhost:8080".to_string()
+}
+
+fn secs<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Option<Duration>, D::Error> {
+    Ok(Option::<u64>::deserialize(deserializer)?.map(Duration::from_secs))
+}
+
+fn default_base_url() -> String {
+    DEFAULT_BASE_URL.to_string()
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Session {
+            client_id: String::new(),
+            client_secret: String::new(),
+            access_token: None,
+            refresh_token: None,
+            token_expires_at: None,
+            token_store: None,
+            base_url: default_base_url(),
+            auth_url: None,
+            token_url: None,
+            token_info_url: None,
+            grant_type: GrantType::default(),
+            scopes: default_scopes(),
+            use_pkce: false,
+            interactive: default_interactive(),
+            authorize_callback: None,
+            redirect_url: default_redirect_url(),
+            bind_address: None,
+            login_timeout: None,
+            rate_limiter: RateLimiter::default(),
+            retry_policy: RetryPolicy::default(),
+            http: HttpConfig::default(),
+            client: reqwest::Client::default(),
+        }
+    }
 }
 
 #[derive(Error, Debug)]
@@ -23,26 +143,151 @@ pub enum SessionError {
     ParseUrlError(#[from] url::ParseError),
     #[error(transparent)]
     ReqwestError(#[from] reqwest::Error),
-    #[error(transparent)]
-    JsonError(#[from] serde_json::Error),
+    #[error("Error: failed to parse response of {path}: {source}")]
+    JsonError {
+        path: String,
+        #[source]
+        source: serde_json::Error,
+    },
     #[error("Error: toml Error")]
     TomlError(#[from] toml::de::Error),
+    #[error("Error: missing {field}, looked in {sources}")]
+    MissingConfig {
+        field: &'static str,
+        sources: String,
+    },
     #[error("Error: Not valide token Error")]
     TokenNotValid,
     #[error("Error: NoneError")]
     NoneError,
+    #[error("Error: Refusing to follow {url}, it is not on the API host")]
+    UntrustedLink { url: String },
     #[error("Error: Server Unauthorized")]
     UnauthorizedServerError,
-    #[error("Error: 403 Fobidden Access")]
-    Fobidden,
-    #[error("Error: 404 Page or resource is not found")]
-    NotFound,
+    #[error("Error: OAuth state does not match, authorization rejected")]
+    CsrfMismatch,
+    #[error("Error: Unknown OAuth scope {0}")]
+    UnknownScope(String),
+    #[error("Error: No login completed within {0:?}")]
+    LoginTimeout(Duration),
+    #[error("Error: Authentication required, browse to {url}")]
+    AuthenticationRequired { url: String },
+    #[error("Error: Invalid slot, {0}")]
+    InvalidSlot(String),
+    #[error("Error: 401 Unauthorized: {0}")]
+    Unauthorized(ErrorResponse),
+    #[error("Error: 403 Forbidden Access: {0}")]
+    Forbidden(ErrorResponse),
+    #[error("Error: 404 Page or resource is not found: {0}")]
+    NotFound(ErrorResponse),
+    #[error("Error: 429 Too Many Requests: {response}")]
+    RateLimited {
+        /// Delay requested by the `Retry-After` header.
+        retry_after: Option<Duration>,
+        response: ErrorResponse,
+    },
+    #[error("Error: Server Error: {0}")]
+    ServerError(ErrorResponse),
+    #[error("Error: Unexpected Response: {0}")]
+    HttpError(ErrorResponse),
+}
+
+impl SessionError {
+    /// The API response behind this error, if it comes from a non-2xx status.
+    pub fn response(&self) -> Option<&ErrorResponse> {
+        match self {
+            SessionError::Unauthorized(response)
+            | SessionError::Forbidden(response)
+            | SessionError::NotFound(response)
+            | SessionError::RateLimited { response, .. }
+            | SessionError::ServerError(response)
+            | SessionError::HttpError(response) => Some(response),
+            _ => None,
+        }
+    }
+    pub fn status(&self) -> Option<StatusCode> {
+        self.response().map(|response| response.status)
+    }
+}
+
+/// A non-2xx response of the API.
+#[derive(Clone, Debug)]
+pub struct ErrorResponse {
+    pub status: StatusCode,
+    /// Path or url of the failed request.
+    pub path: String,
+    pub body: String,
+    /// `error` field of the intra error JSON.
+    pub error: Option<String>,
+    /// `message` field of the intra error JSON.
+    pub message: Option<String>,
+}
+
+impl ErrorResponse {
+    fn new(status: StatusCode, path: &str, body: String) -> Self {
+        let json: Option<serde_json::Value> = serde_json::from_str(&body).ok();
+        let field = |name: &str| {
+            json.as_ref()
+                .and_then(|json| json.get(name))
+                .map(|value| match value {
+                    serde_json::Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                })
+        };
+        ErrorResponse {
+            status,
+            path: path.to_string(),
+            error: field("error"),
+            message: field("message"),
+            body,
+        }
+    }
+}
+
+impl fmt::Display for ErrorResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} on {}", self.status, self.path)?;
+        if let Some(error) = &self.error {
+            write!(f, ": {}", error)?;
+        }
+        if let Some(message) = &self.message {
+            write!(f, ": {}", message)?;
+        }
+        Ok(())
+    }
+}
+
+/// Headers and body of a successful API response, read within the retry loop.
+pub(crate) struct Reply {
+    pub(crate) headers: HeaderMap,
+    pub(crate) body: String,
+}
+
+/// Maps a non-2xx status to the matching `SessionError`.
+fn status_error(
+    status: StatusCode,
+    retry_after: Option<Duration>,
+    response: ErrorResponse,
+) -> SessionError {
+    match status {
+        StatusCode::UNAUTHORIZED => SessionError::Unauthorized(response),
+        StatusCode::FORBIDDEN => SessionError::Forbidden(response),
+        StatusCode::NOT_FOUND => SessionError::NotFound(response),
+        StatusCode::TOO_MANY_REQUESTS => SessionError::RateLimited {
+            retry_after,
+            response,
+        },
+        s if s.is_server_error() => SessionError::ServerError(response),
+        _ => SessionError::HttpError(response),
+    }
 }
 
 impl Session {
     pub fn new(path: &str) -> Result<Self, SessionError> {
-        let content = fs::read_to_string(path)?;
-        Ok(toml::from_str(&content)?)
+        SessionBuilder::new().config_file(path).build()
+    }
+    pub fn builder() -> SessionBuilder {
+        SessionBuilder::new()
     }
     pub fn get_client_id(&self) -> &str {
         self.client_id.as_str()
@@ -55,56 +300,596 @@ impl Session {
     }
     pub fn set_access_token(&mut self, token: String) {
         self.access_token = Some(token);
+        self.token_expires_at = None;
     }
     pub fn update_access_token(&mut self, token: String) {
         self.access_token = Some(token);
+        self.token_expires_at = None;
+    }
+    /// Unix timestamp at which the access token expires, when known.
+    pub fn get_token_expires_at(&self) -> Option<i64> {
+        self.token_expires_at
+    }
+    pub fn get_refresh_token(&self) -> Option<String> {
+        self.refresh_token.clone()
+    }
+    pub fn set_refresh_token(&mut self, token: String) {
+        self.refresh_token = Some(token);
+    }
+    pub fn get_token_store(&self) -> Option<&Arc<dyn TokenStore>> {
+        self.token_store.as_ref()
+    }
+    /// Persists tokens in `store`, also reading it back when no token is set.
+    pub fn set_token_store(&mut self, store: Arc<dyn TokenStore>) {
+        self.token_store = Some(store);
+    }
+    pub fn get_base_url(&self) -> &str {
+        self.base_url.as_str()
+    }
+    pub fn set_base_url(&mut self, url: String) {
+        self.base_url = url;
+    }
+    /// OAuth authorize endpoint, `{base_url}/oauth/authorize` unless configured.
+    pub fn get_auth_url(&self) -> String {
+        match &self.auth_url {
+            Some(url) => url.clone(),
+            None => self.endpoint("oauth/authorize"),
+        }
+    }
+    pub fn set_auth_url(&mut self, url: String) {
+        self.auth_url = Some(url);
+    }
+    /// OAuth token endpoint, `{base_url}/oauth/token` unless configured.
+    pub fn get_token_url(&self) -> String {
+        match &self.token_url {
+            Some(url) => url.clone(),
+            None => self.endpoint("oauth/token"),
+        }
+    }
+    pub fn set_token_url(&mut self, url: String) {
+        self.token_url = Some(url);
+    }
+    /// Token info endpoint, `{base_url}/oauth/token/info` unless configured.
+    pub fn get_token_info_url(&self) -> String {
+        match &self.token_info_url {
+            Some(url) => url.clone(),
+            None => self.endpoint("oauth/token/info"),
+        }
+    }
+    pub fn set_token_info_url(&mut self, url: String) {
+        self.token_info_url = Some(url);
+    }
+    pub fn get_grant_type(&self) -> GrantType {
+        self.grant_type
+    }
+    pub fn set_grant_type(&mut self, grant_type: GrantType) {
+        self.grant_type = grant_type;
+    }
+    pub fn get_scopes(&self) -> &[String] {
+        self.scopes.as_slice()
+    }
+    pub fn set_scopes(&mut self, scopes: Vec<String>) {
+        self.scopes = scopes;
+    }
+    pub fn get_use_pkce(&self) -> bool {
+        self.use_pkce
+    }
+    /// Adds a PKCE challenge to the authorization code flow.
+    pub fn set_use_pkce(&mut self, use_pkce: bool) {
+        self.use_pkce = use_pkce;
+    }
+    pub fn is_interactive(&self) -> bool {
+        self.interactive
+    }
+    /// When `false`, a needed browser login fails with `AuthenticationRequired`
+    /// instead of waiting on the local server.
+    pub fn set_interactive(&mut self, interactive: bool) {
+        self.interactive = interactive;
+    }
+    pub fn get_authorize_callback(&self) -> Option<&AuthorizeCallback> {
+        self.authorize_callback.as_ref()
+    }
+    /// Hands the authorization url to `callback` instead of printing it.
+    pub fn set_authorize_callback<F: Fn(&url::Url) + Send + Sync + 'static>(
+        &mut self,
+        callback: F,
+    ) {
+        self.authorize_callback = Some(AuthorizeCallback::new(callback));
+    }
+    pub fn get_redirect_url(&self) -> &str {
+        self.redirect_url.as_str()
+    }
+    /// Redirect url registered for the application, `http://localhost:8080` by default.
+    pub fn set_redirect_url(&mut self, url: String) {
+        self.redirect_url = url;
+    }
+    /// Address the login server listens on, `127.0.0.1` on the redirect port unless configured.
+    pub fn get_bind_address(&self) -> Result<String, SessionError> {
+        if let Some(address) = &self.bind_address {
+            return Ok(address.clone());
+        }
+        let url = url::Url::parse(&self.redirect_url)?;
+        let port = url.port_or_known_default().ok_or(SessionError::NoneError)?;
+        Ok(format!("127.0.0.1:{}", port))
+    }
+    pub fn set_bind_address(&mut self, address: String) {
+        self.bind_address = Some(address);
+    }
+    pub fn get_login_timeout(&self) -> Option<Duration> {
+        self.login_timeout
+    }
+    /// Gives up on the interactive login after `timeout`.
+    pub fn set_login_timeout(&mut self, timeout: Duration) {
+        self.login_timeout = Some(timeout);
+    }
+    /// Limiter shared by this session and all of its clones.
+    pub fn get_rate_limiter(&self) -> &RateLimiter {
+        &self.rate_limiter
+    }
+    /// Replaces the limiter, detaching this session from the previous one.
+    pub fn set_rate_limit(&mut self, limit: RateLimit) {
+        self.rate_limiter = RateLimiter::new(limit);
+    }
+    pub fn get_retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) {
+        self.retry_policy = policy;
+    }
+    pub fn get_http_config(&self) -> &HttpConfig {
+        &self.http
+    }
+    /// Pooled client used for both API and OAuth requests.
+    pub fn get_http_client(&self) -> &reqwest::Client {
+        &self.client
+    }
+    /// Joins `uri` onto the configured base url, absolute urls are kept as is.
+    fn endpoint(&self, uri: &str) -> String {
+        if uri.starts_with("http://") || uri.starts_with("https://") {
+            return uri.to_string();
+        }
+        format!(
+            "{}/{}",
+            self.base_url.trim_end_matches('/'),
+            uri.trim_start_matches('/')
+        )
     }
 }
 
 impl Session {
     pub async fn call(&mut self, uri: &str) -> Result<String, SessionError> {
-        if !(check_token_valide(self.get_access_token()).await?) {
-            let token = generate_token(self.clone()).await?;
-            self.set_access_token(token);
-            if let Ok(false) = check_token_valide(self.get_access_token()).await {
-                println!("Token is not valid, please check access token.");
-                return Err(SessionError::TokenNotValid);
+        Ok(self.send(uri).await?.body)
+    }
+
+    /// Same as `call`, retrying according to `policy` instead of the session policy.
+    pub async fn call_with_retry(
+        &mut self,
+        uri: &str,
+        policy: &RetryPolicy,
+    ) -> Result<String, SessionError> {
+        Ok(self.request(Method::GET, uri, &[], policy).await?.body)
+    }
+
+    /// Sends an authorized GET request for `uri` with the session retry policy.
+    async fn send(&mut self, uri: &str) -> Result<Reply, SessionError> {
+        let policy = self.retry_policy.clone();
+        self.request(Method::GET, uri, &[], &policy).await
+    }
+
+    /// Sends an authorized request, retrying according to `policy`.
+    ///
+    /// POST requests are only retried on 429, when the API did not process them.
+    async fn request(
+        &mut self,
+        method: Method,
+        uri: &str,
+        form: &[(String, String)],
+        policy: &RetryPolicy,
+    ) -> Result<Reply, SessionError> {
+        self.ensure_token().await?;
+        let mut attempt = 1;
+        let mut reauthorized = false;
+        loop {
+            match self.send_once(method.clone(), uri, form).await {
+                // The token may have been revoked or expired earlier than recorded.
+                Err(SessionError::Unauthorized(_)) if !reauthorized => {
+                    warn!("call(): 401 on {}, revalidating the access token", uri);
+                    reauthorized = true;
+                    self.token_expires_at = None;
+                    self.ensure_token().await?;
+                }
+                Err(err)
+                    if method == Method::POST
+                        && !matches!(err, SessionError::RateLimited { .. }) =>
+                {
+                    return Err(err)
+                }
+                Err(err) => match policy.delay(attempt, &err) {
+                    Some(delay) => {
+                        warn!(
+                            "call(): attempt {} failed ({}), retrying in {:?}",
+                            attempt, err, delay
+                        );
+                        tokio::time::sleep(delay).await;
+                        attempt += 1;
+                    }
+                    None => return Err(err),
+                },
+                response => return response,
             }
         }
-        let ac_token = self.get_access_token().unwrap_or_default();
-        let client = reqwest::Client::new();
-        let params = [
-            ("grant_type", "client_credentials"),
-            ("client_id", self.get_client_id()),
-        ];
-        let response = client
-            .get(format!("https://api.intra.42.fr/{}", uri))
-            .header(AUTHORIZATION, format!("Bearer {}", ac_token))
-            .form(&params)
-            .send()
-            .await?;
-
-        match response.status() {
-            reqwest::StatusCode::OK => {
-                debug!("call(): reqwest OK");
-            }
-            reqwest::StatusCode::UNAUTHORIZED => {
-                warn!("call(): unauthorized");
-                return Err(SessionError::UnauthorizedServerError);
+    }
+
+    /// Makes sure the session holds a valid access token.
+    ///
+    /// A token whose expiry is known and not close is used without asking the
+    /// API. Otherwise tries, in order, the token info endpoint, the refresh
+    /// token and finally a new authorization.
+    async fn ensure_token(&mut self) -> Result<(), SessionError> {
+        if self.access_token.is_none() {
+            if let Some(token) = self.load_token()? {
+                self.apply_token(token);
             }
-            reqwest::StatusCode::FORBIDDEN => {
-                warn!("call(): 402 FORBIDDEN ACCESS");
-                return Err(SessionError::Fobidden);
+        }
+        if self.token_is_fresh() || self.validate_token().await? {
+            return Ok(());
+        }
+        if let Some(refresh) = self.get_refresh_token() {
+            match refresh_token(self, &refresh).await {
+                Ok(token) => {
+                    self.save_token(token)?;
+                    if self.token_is_fresh() || self.validate_token().await? {
+                        return Ok(());
+                    }
+                }
+                Err(err) => warn!("ensure_token(): refresh failed: {}", err),
             }
-            reqwest::StatusCode::NOT_FOUND => {
-                warn!("404 NOT FOUND");
-                return Err(SessionError::NotFound);
+        }
+        let token = generate_token(self.clone()).await?;
+        self.save_token(token)?;
+        if !self.token_is_fresh() && !self.validate_token().await? {
+            warn!("ensure_token(): token is not valid, please check access token");
+            return Err(SessionError::TokenNotValid);
+        }
+        Ok(())
+    }
+
+    /// Whether the token is known to stay valid for more than `TOKEN_EXPIRY_MARGIN`.
+    fn token_is_fresh(&self) -> bool {
+        match (&self.access_token, self.token_expires_at) {
+            (Some(_), Some(expires_at)) => {
+                unix_now() + (TOKEN_EXPIRY_MARGIN.as_secs() as i64) < expires_at
             }
-            _ => {
-                panic!("uh oh! something unexpected happened");
+            _ => false,
+        }
+    }
+
+    /// Asks the token info endpoint about the token and records its expiry.
+    async fn validate_token(&mut self) -> Result<bool, SessionError> {
+        let info = token_info(self, self.get_access_token()).await?;
+        match info.expires_in_seconds {
+            Some(expires_in) => {
+                self.token_expires_at = Some(unix_now() + expires_in);
+                Ok(true)
             }
+            None => Ok(false),
+        }
+    }
+
+    fn load_token(&self) -> Result<Option<StoredToken>, SessionError> {
+        match &self.token_store {
+            Some(store) => store.load(),
+            None => Ok(None),
+        }
+    }
+
+    fn save_token(&mut self, token: StoredToken) -> Result<(), SessionError> {
+        if let Some(store) = &self.token_store {
+            store.save(&token)?;
         }
-        let tmp = response.text().await?;
-        Ok(tmp)
+        self.apply_token(token);
+        Ok(())
     }
+
+    fn apply_token(&mut self, token: StoredToken) {
+        self.access_token = Some(token.access_token);
+        self.token_expires_at = token.expires_at;
+        if token.refresh_token.is_some() {
+            self.refresh_token = token.refresh_token;
+        }
+    }
+
+    /// Sends a single request for `uri`, checks the response status and reads the body.
+    async fn send_once(
+        &mut self,
+        method: Method,
+        uri: &str,
+        form: &[(String, String)],
+    ) -> Result<Reply, SessionError> {
+        let ac_token = self.get_access_token().unwrap_or_default();
+        self.rate_limiter.acquire().await;
+        let builder = self
+            .client
+            .request(method.clone(), self.endpoint(uri))
+            .header(AUTHORIZATION, format!("Bearer {}", ac_token));
+        let builder = if method == Method::GET {
+            let params = [
+                ("grant_type", "client_credentials"),
+                ("client_id", self.get_client_id()),
+            ];
+            builder.form(&params)
+        } else {
+            builder.form(form)
+        };
+        let response = builder.send().await?;
+        self.rate_limiter.update_from_headers(response.headers());
+
+        let status = response.status();
+        if status.is_success() {
+            debug!("call(): reqwest {}", status);
+            let headers = response.headers().clone();
+            let body = response.text().await?;
+            return Ok(Reply { headers, body });
+        }
+        warn!("call(): {} on {}", status, uri);
+        let retry_after = response
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse().ok())
+            .map(Duration::from_secs);
+        let body = response.text().await.unwrap_or_default();
+        Err(status_error(
+            status,
+            retry_after,
+            ErrorResponse::new(status, uri, body),
+        ))
+    }
+}
+
+impl Session {
+    /// Requests `path` and deserializes the JSON response into `T`.
+    pub async fn get<T: DeserializeOwned>(&mut self, path: &str) -> Result<T, SessionError> {
+        let body = self.call(path).await?;
+        from_json(path, &body)
+    }
+    /// Same as `get`, retrying according to `policy` instead of the session policy.
+    pub async fn get_with_retry<T: DeserializeOwned>(
+        &mut self,
+        path: &str,
+        policy: &RetryPolicy,
+    ) -> Result<T, SessionError> {
+        let body = self.call_with_retry(path, policy).await?;
+        from_json(path, &body)
+    }
+    /// POSTs `form` to `path` and deserializes the JSON response into `T`.
+    pub async fn post<T: DeserializeOwned>(
+        &mut self,
+        path: &str,
+        form: &[(String, String)],
+    ) -> Result<T, SessionError> {
+        let policy = self.retry_policy.clone();
+        let reply = self.request(Method::POST, path, form, &policy).await?;
+        from_json(path, &reply.body)
+    }
+    /// Sends a DELETE request to `path`.
+    pub async fn delete(&mut self, path: &str) -> Result<(), SessionError> {
+        let policy = self.retry_policy.clone();
+        self.request(Method::DELETE, path, &[], &policy).await?;
+        Ok(())
+    }
+    /// `/v2/me`: the user who owns the access token.
+    pub async fn me(&mut self) -> Result<Me, SessionError> {
+        self.get("v2/me").await
+    }
+    /// `/v2/campus`: every campus of the network.
+    pub async fn campuses(&mut self) -> Result<Campus, SessionError> {
+        self.paginate("v2/campus").collect_all().await
+    }
+}
+
+fn from_json<T: DeserializeOwned>(path: &str, body: &str) -> Result<T, SessionError> {
+    serde_json::from_str(body).map_err(|source| SessionError::JsonError {
+        path: path.to_string(),
+        source,
+    })
+}
+
+#[test]
+fn session_default_urls_test() {
+    let session: Session = toml::from_str(
+        r#"
+        client_id = "id"
+        client_secret = "secret"
+        "#,
+    )
+    .unwrap();
+    assert_eq!(session.get_base_url(), DEFAULT_BASE_URL);
+    assert_eq!(
+        session.get_token_url(),
+        "https://api.intra.42.fr/oauth/token"
+    );
+    assert_eq!(session.endpoint("/v2/me"), "https://api.intra.42.fr/v2/me");
+    assert_eq!(session.get_grant_type(), GrantType::AuthorizationCode);
+    assert_eq!(session.get_scopes(), ["public"]);
+    assert_eq!(session.get_redirect_url(), "http://localhost:8080");
+    assert_eq!(session.get_bind_address().unwrap(), "127.0.0.1:8080");
+    assert_eq!(session.get_login_timeout(), None);
+}
+
+#[test]
+fn session_login_server_test() {
+    let session: Session = toml::from_str(
+        r#"
+        client_id = "id"
+        client_secret = "secret"
+        redirect_url = "http://localhost:4242/callback"
+        login_timeout_secs = 120
+        interactive = false
+        "#,
+    )
+    .unwrap();
+    assert!(!session.is_interactive());
+    assert_eq!(session.get_bind_address().unwrap(), "127.0.0.1:4242");
+    assert_eq!(session.get_login_timeout(), Some(Duration::from_secs(120)));
+}
+
+#[test]
+fn session_client_credentials_test() {
+    let session: Session = toml::from_str(
+        r#"
+        client_id = "id"
+        client_secret = "secret"
+        grant_type = "client_credentials"
+        scopes = ["public", "projects"]
+        use_pkce = true
+        "#,
+    )
+    .unwrap();
+    assert_eq!(session.get_grant_type(), GrantType::ClientCredentials);
+    assert_eq!(session.get_scopes(), ["public", "projects"]);
+    assert!(session.get_use_pkce());
+    assert_eq!(session.get_rate_limiter().limit(), RateLimit::default());
+}
+
+#[test]
+fn session_rate_limit_test() {
+    let session: Session = toml::from_str(
+        r#"
+        client_id = "id"
+        client_secret = "secret"
+
+        [rate_limit]
+        per_second = 8
+        "#,
+    )
+    .unwrap();
+    let limit = session.get_rate_limiter().limit();
+    assert_eq!(limit.per_second, 8);
+    assert_eq!(limit.per_hour, 1200);
+}
+
+#[test]
+fn session_custom_urls_test() {
+    let session: Session = toml::from_str(
+        r#"
+        client_id = "id"
+        client_secret = "secret"
+        base_url = "http://127.0.0.1:3000/"
+        token_info_url = "http://127.0.0.1:4000/info"
+        "#,
+    )
+    .unwrap();
+    assert_eq!(session.endpoint("v2/me"), "http://127.0.0.1:3000/v2/me");
+    assert_eq!(
+        session.get_auth_url(),
+        "http://127.0.0.1:3000/oauth/authorize"
+    );
+    assert_eq!(session.get_token_info_url(), "http://127.0.0.1:4000/info");
+}
+
+#[test]
+fn from_json_error_has_path_test() {
+    let res: Result<Me, SessionError> = from_json("v2/me", "{\"id\": \"not a number\"}");
+    match res {
+        Err(SessionError::JsonError { path, .. }) => assert_eq!(path, "v2/me"),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn status_error_test() {
+    let body = r#"{"error":"Not Found","message":"The page you are looking for doesn't exist"}"#;
+    let response = ErrorResponse::new(StatusCode::NOT_FOUND, "v2/users/nobody", body.to_string());
+    assert_eq!(response.error.as_deref(), Some("Not Found"));
+    assert!(matches!(
+        status_error(StatusCode::NOT_FOUND, None, response),
+        SessionError::NotFound(_)
+    ));
+
+    let response = ErrorResponse::new(StatusCode::TOO_MANY_REQUESTS, "v2/me", String::new());
+    match status_error(
+        StatusCode::TOO_MANY_REQUESTS,
+        Some(Duration::from_secs(3)),
+        response,
+    ) {
+        SessionError::RateLimited { retry_after, .. } => {
+            assert_eq!(retry_after, Some(Duration::from_secs(3)))
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+
+    let body = r#"{"error":{"begin_at":["is invalid"]}}"#;
+    let response = ErrorResponse::new(
+        StatusCode::UNPROCESSABLE_ENTITY,
+        "v2/slots",
+        body.to_string(),
+    );
+    assert_eq!(
+        response.error.as_deref(),
+        Some(r#"{"begin_at":["is invalid"]}"#)
+    );
+    assert!(matches!(
+        status_error(StatusCode::UNPROCESSABLE_ENTITY, None, response),
+        SessionError::HttpError(_)
+    ));
+    let response = ErrorResponse::new(StatusCode::BAD_GATEWAY, "v2/me", String::new());
+    assert!(matches!(
+        status_error(StatusCode::BAD_GATEWAY, None, response),
+        SessionError::ServerError(_)
+    ));
+}
+
+#[test]
+fn session_token_file_test() {
+    let session: Session = toml::from_str(
+        r#"
+        client_id = "id"
+        client_secret = "secret"
+        token_file = "/tmp/ftapi-token.json"
+        "#,
+    )
+    .unwrap();
+    assert!(session.get_token_store().is_some());
+}
+
+#[test]
+fn session_load_token_test() {
+    let mut session = Session::default();
+    let store = Arc::new(store::MemoryTokenStore::new());
+    session.set_token_store(store.clone());
+    session
+        .save_token(StoredToken {
+            access_token: "access".to_string(),
+            refresh_token: Some("refresh".to_string()),
+            ..StoredToken::default()
+        })
+        .unwrap();
+    assert_eq!(store.load().unwrap().unwrap().access_token, "access");
+
+    let mut other = Session::default();
+    other.set_token_store(store);
+    let token = other.load_token().unwrap().unwrap();
+    other.apply_token(token);
+    assert_eq!(other.get_access_token().as_deref(), Some("access"));
+    assert_eq!(other.get_refresh_token().as_deref(), Some("refresh"));
+}
+
+#[tokio::test]
+async fn ensure_token_skips_fresh_token_test() {
+    let mut session = Session::default();
+    session.set_base_url("http://127.0.0.1:9".to_string());
+    session
+        .save_token(StoredToken {
+            access_token: "access".to_string(),
+            expires_at: Some(unix_now() + 3600),
+            ..StoredToken::default()
+        })
+        .unwrap();
+    assert!(session.token_is_fresh());
+    // No request is made, the unreachable base url would fail otherwise.
+    assert!(session.ensure_token().await.is_ok());
+
+    session.set_access_token("other".to_string());
+    assert!(!session.token_is_fresh());
 }
@@ -0,0 +1,211 @@
+use crate::{from_json, Session, SessionError};
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
+use reqwest::header::LINK;
+use serde::de::DeserializeOwned;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+/// Largest `page[size]` accepted by the 42 API.
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// One page of a list endpoint.
+#[derive(Debug)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Number of items across all pages, read from `X-Total`.
+    pub total: Option<u64>,
+    /// Url of the following page, read from `Link: rel="next"`.
+    pub next: Option<String>,
+}
+
+/// Stream over every item of a paginated endpoint.
+///
+/// Pages are only requested while the stream is polled, so dropping it or
+/// using `StreamExt::take` stops the pagination early.
+pub struct Paginator<'a, T> {
+    stream: Pin<Box<dyn Stream<Item = Result<T, SessionError>> + Send + 'a>>,
+    total: Arc<Mutex<Option<u64>>>,
+}
+
+impl<'a, T> Paginator<'a, T> {
+    /// Total number of items, known once the first page has been fetched.
+    pub fn total(&self) -> Option<u64> {
+        *self.total.lock().unwrap()
+    }
+    /// Fetches every remaining page and collects the items.
+    pub async fn collect_all(self) -> Result<Vec<T>, SessionError> {
+        self.try_collect().await
+    }
+}
+
+impl<'a, T> Stream for Paginator<'a, T> {
+    type Item = Result<T, SessionError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.stream.as_mut().poll_next(cx)
+    }
+}
+
+impl Session {
+    /// Fetches a single page of a list endpoint.
+    pub async fn get_page<T: DeserializeOwned>(
+        &mut self,
+        path: &str,
+    ) -> Result<Page<T>, SessionError> {
+        let reply = self.send(path).await?;
+        let total = reply
+            .headers
+            .get("X-Total")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse().ok());
+        let next = reply
+            .headers
+            .get_all(LINK)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .find_map(next_link);
+        let items = from_json(path, &reply.body)?;
+        Ok(Page { items, total, next })
+    }
+
+    /// Streams every item of the list endpoint `path`, following `Link` headers.
+    ///
+    /// `page[size]` defaults to [`MAX_PAGE_SIZE`] unless `path` already sets it.
+    pub fn paginate<'a, T>(&'a mut self, path: &str) -> Paginator<'a, T>
+    where
+        T: DeserializeOwned + Send + 'a,
+    {
+        let total = Arc::new(Mutex::new(None));
+        let first = with_page_size(path);
+        let shared_total = Arc::clone(&total);
+        let pages = stream::try_unfold((self, Some(first)), move |(session, next)| {
+            let total = Arc::clone(&shared_total);
+            async move {
+                let path = match next {
+                    Some(path) => path,
+                    None => return Ok::<_, SessionError>(None),
+                };
+                if !same_origin(&path, session.get_base_url()) {
+                    return Err(SessionError::UntrustedLink { url: path });
+                }
+                let page: Page<T> = session.get_page(&path).await?;
+                if page.total.is_some() {
+                    *total.lock().unwrap() = page.total;
+                }
+                Ok(Some((page.items, (session, page.next))))
+            }
+        });
+        let items = pages
+            .map_ok(|items| stream::iter(items.into_iter().map(Ok)))
+            .try_flatten();
+        Paginator {
+            stream: items.boxed(),
+            total,
+        }
+    }
+}
+
+/// Appends url encoded `params` to the query of `path`.
+pub(crate) fn with_params(path: &str, params: &[(String, String)]) -> String {
+    if params.is_empty() {
+        return path.to_string();
+    }
+    let query = url::form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(params)
+        .finish();
+    let separator = if path.contains('?') { '&' } else { '?' };
+    format!("{}{}{}", path, separator, query)
+}
+
+fn with_page_size(path: &str) -> String {
+    if path.contains("page[size]") {
+        return path.to_string();
+    }
+    let separator = if path.contains('?') { '&' } else { '?' };
+    format!("{}{}page[size]={}", path, separator, MAX_PAGE_SIZE)
+}
+
+/// Whether `link` points at the same scheme, host and port as `base_url`.
+///
+/// Relative links are always joined onto the base url, so they match.
+fn same_origin(link: &str, base_url: &str) -> bool {
+    if !link.starts_with("http://") && !link.starts_with("https://") {
+        return true;
+    }
+    match (url::Url::parse(link), url::Url::parse(base_url)) {
+        (Ok(link), Ok(base)) => link.origin() == base.origin(),
+        _ => false,
+    }
+}
+
+/// Extracts the `rel="next"` target of a `Link` header.
+fn next_link(header: &str) -> Option<String> {
+    header.split(',').find_map(|link| {
+        let mut parts = link.split(';');
+        let url = parts.next()?.trim();
+        let is_next = parts.any(|param| {
+            let param = param.trim();
+            param == "rel=\"next\"" || param == "rel=next"
+        });
+        if is_next {
+            Some(
+                url.trim_start_matches('<')
+                    .trim_end_matches('>')
+                    .to_string(),
+            )
+        } else {
+            None
+        }
+    })
+}
+
+#[test]
+fn next_link_test() {
+    let header = "<https://api.intra.42.fr/v2/campus?page=3>; rel=\"next\", \
+                  <https://api.intra.42.fr/v2/campus?page=1>; rel=\"prev\"";
+    assert_eq!(
+        next_link(header),
+        Some("https://api.intra.42.fr/v2/campus?page=3".to_string())
+    );
+    assert_eq!(
+        next_link("<https://api.intra.42.fr/v2/campus?page=1>; rel=\"first\""),
+        None
+    );
+}
+
+#[test]
+fn same_origin_test() {
+    let base = "https://api.intra.42.fr";
+    assert!(same_origin("v2/campus?page[size]=100", base));
+    assert!(same_origin(
+        "https://api.intra.42.fr/v2/campus?page=2",
+        base
+    ));
+    assert!(same_origin(
+        "https://api.intra.42.fr:443/v2/campus?page=2",
+        base
+    ));
+    assert!(!same_origin("https://evil.example/v2/campus?page=2", base));
+    assert!(!same_origin(
+        "http://api.intra.42.fr/v2/campus?page=2",
+        base
+    ));
+    assert!(!same_origin(
+        "https://api.intra.42.fr:8443/v2/campus?page=2",
+        base
+    ));
+}
+
+#[test]
+fn with_page_size_test() {
+    assert_eq!(with_page_size("v2/campus"), "v2/campus?page[size]=100");
+    assert_eq!(
+        with_page_size("v2/campus?sort=name"),
+        "v2/campus?sort=name&page[size]=100"
+    );
+    assert_eq!(
+        with_page_size("v2/campus?page[size]=5"),
+        "v2/campus?page[size]=5"
+    );
+}
@@ -0,0 +1,182 @@
+use reqwest::header::HeaderMap;
+use serde::Deserialize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Request quotas enforced by [`RateLimiter`]. A limit of `0` disables it.
+///
+/// Defaults match the quotas the intra grants to a single application.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+pub struct RateLimit {
+    #[serde(default = "default_per_second")]
+    pub per_second: u32,
+    #[serde(default = "default_per_hour")]
+    pub per_hour: u32,
+}
+
+fn default_per_second() -> u32 {
+    2
+}
+
+fn default_per_hour() -> u32 {
+    1200
+}
+
+impl Default for RateLimit {
+    fn default() -> Self {
+        RateLimit {
+            per_second: default_per_second(),
+            per_hour: default_per_hour(),
+        }
+    }
+}
+
+/// Token bucket limiter shared by every clone of a `Session`.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(from = "RateLimit")]
+pub struct RateLimiter {
+    limit: RateLimit,
+    buckets: Arc<Mutex<Buckets>>,
+}
+
+#[derive(Debug)]
+struct Buckets {
+    secondly: Bucket,
+    hourly: Bucket,
+}
+
+impl Default for Buckets {
+    fn default() -> Self {
+        Buckets::new(RateLimit::default(), Instant::now())
+    }
+}
+
+impl Buckets {
+    fn new(limit: RateLimit, now: Instant) -> Self {
+        Buckets {
+            secondly: Bucket::new(limit.per_second, Duration::from_secs(1), now),
+            hourly: Bucket::new(limit.per_hour, Duration::from_secs(3600), now),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    capacity: f64,
+    tokens: f64,
+    per_sec: f64,
+    last: Instant,
+}
+
+impl Bucket {
+    fn new(limit: u32, period: Duration, now: Instant) -> Self {
+        Bucket {
+            capacity: limit as f64,
+            tokens: limit as f64,
+            per_sec: limit as f64 / period.as_secs_f64(),
+            last: now,
+        }
+    }
+    fn unlimited(&self) -> bool {
+        self.capacity == 0.0
+    }
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.per_sec).min(self.capacity);
+        self.last = now;
+    }
+    /// Time left before one token is available.
+    fn wait(&self) -> Duration {
+        if self.unlimited() || self.tokens >= 1.0 {
+            return Duration::ZERO;
+        }
+        Duration::from_secs_f64((1.0 - self.tokens) / self.per_sec)
+    }
+    fn take(&mut self) {
+        if !self.unlimited() {
+            self.tokens -= 1.0;
+        }
+    }
+    fn resync(&mut self, remaining: u32) {
+        if !self.unlimited() {
+            self.tokens = self.tokens.min(remaining as f64);
+        }
+    }
+}
+
+impl From<RateLimit> for RateLimiter {
+    fn from(limit: RateLimit) -> Self {
+        RateLimiter::new(limit)
+    }
+}
+
+impl RateLimiter {
+    pub fn new(limit: RateLimit) -> Self {
+        RateLimiter {
+            limit,
+            buckets: Arc::new(Mutex::new(Buckets::new(limit, Instant::now()))),
+        }
+    }
+    pub fn limit(&self) -> RateLimit {
+        self.limit
+    }
+    /// Waits until both quotas allow one more request and consumes it.
+    pub async fn acquire(&self) {
+        while let Err(wait) = self.try_acquire(Instant::now()) {
+            tokio::time::sleep(wait).await;
+        }
+    }
+    /// Consumes a request if allowed at `now`, otherwise returns how long to wait.
+    fn try_acquire(&self, now: Instant) -> Result<(), Duration> {
+        let mut buckets = self.buckets.lock().unwrap();
+        buckets.secondly.refill(now);
+        buckets.hourly.refill(now);
+        let wait = buckets.secondly.wait().max(buckets.hourly.wait());
+        if !wait.is_zero() {
+            return Err(wait);
+        }
+        buckets.secondly.take();
+        buckets.hourly.take();
+        Ok(())
+    }
+    /// Lowers the local quotas to what the API reports as remaining.
+    pub fn update_from_headers(&self, headers: &HeaderMap) {
+        let remaining =
+            |name: &str| -> Option<u32> { headers.get(name)?.to_str().ok()?.parse().ok() };
+        let mut buckets = self.buckets.lock().unwrap();
+        if let Some(n) = remaining("X-Secondly-Ratelimit-Remaining") {
+            buckets.secondly.resync(n);
+        }
+        if let Some(n) = remaining("X-Hourly-Ratelimit-Remaining") {
+            buckets.hourly.resync(n);
+        }
+    }
+}
+
+#[test]
+fn rate_limiter_per_second_test() {
+    let limiter = RateLimiter::new(RateLimit::default());
+    let now = Instant::now();
+    assert!(limiter.try_acquire(now).is_ok());
+    assert!(limiter.try_acquire(now).is_ok());
+    let wait = limiter.try_acquire(now).unwrap_err();
+    assert!(wait > Duration::ZERO && wait <= Duration::from_millis(500));
+    assert!(limiter
+        .try_acquire(now + Duration::from_millis(500))
+        .is_ok());
+}
+
+#[test]
+fn rate_limiter_shared_and_resync_test() {
+    let limiter = RateLimiter::new(RateLimit {
+        per_second: 0,
+        per_hour: 1200,
+    });
+    let clone = limiter.clone();
+    let mut headers = HeaderMap::new();
+    headers.insert("X-Hourly-Ratelimit-Remaining", "1".parse().unwrap());
+    clone.update_from_headers(&headers);
+    let now = Instant::now();
+    assert!(limiter.try_acquire(now).is_ok());
+    assert!(clone.try_acquire(now).is_err());
+}
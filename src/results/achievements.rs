@@ -0,0 +1,90 @@
+use crate::paginate::Paginator;
+use crate::results::users::UserRef;
+use crate::{Session, SessionError};
+use futures::TryStreamExt;
+use std::collections::HashSet;
+use std::fmt;
+
+pub use crate::results::me::{Achievement, Kind, Tier, Title, TitlesUser};
+
+/// Achievements of a catalog split by whether a user has them.
+#[derive(Debug, Default)]
+pub struct AchievementsDiff {
+    pub obtained: Vec<Achievement>,
+    pub missing: Vec<Achievement>,
+}
+
+impl AchievementsDiff {
+    pub fn new(user_achievements: &[Achievement], catalog: Vec<Achievement>) -> Self {
+        let owned: HashSet<i64> = user_achievements.iter().map(|a| a.id).collect();
+        let (obtained, missing) = catalog.into_iter().partition(|a| owned.contains(&a.id));
+        AchievementsDiff { obtained, missing }
+    }
+}
+
+impl Session {
+    /// `/v2/achievements`
+    pub fn achievements(&mut self) -> Paginator<'_, Achievement> {
+        self.paginate("v2/achievements")
+    }
+    /// `/v2/campus/:id/achievements`
+    pub fn campus_achievements(&mut self, campus_id: i64) -> Paginator<'_, Achievement> {
+        self.paginate(&format!("v2/campus/{}/achievements", campus_id))
+    }
+    /// `/v2/achievements/:id/users`
+    pub fn achievement_users(&mut self, achievement_id: i64) -> Paginator<'_, UserRef> {
+        self.paginate(&format!("v2/achievements/{}/users", achievement_id))
+    }
+    /// `/v2/titles`
+    pub fn titles(&mut self) -> Paginator<'_, Title> {
+        self.paginate("v2/titles")
+    }
+    /// `/v2/titles/:id/users`
+    pub fn title_users(&mut self, title_id: i64) -> Paginator<'_, UserRef> {
+        self.paginate(&format!("v2/titles/{}/users", title_id))
+    }
+    /// Compares a user's achievements with the catalog of a campus.
+    pub async fn user_achievements_diff<T: fmt::Display>(
+        &mut self,
+        login_or_id: T,
+        campus_id: i64,
+    ) -> Result<AchievementsDiff, SessionError> {
+        let user = self.user(login_or_id).await?;
+        let catalog: Vec<Achievement> = self.campus_achievements(campus_id).try_collect().await?;
+        Ok(AchievementsDiff::new(&user.achievements, catalog))
+    }
+}
+
+#[test]
+fn achievements_diff_test() {
+    let catalog = r#"[
+        {"id": 1, "name": "Welcome, Cadet!", "description": "", "tier": "none", "kind": "project",
+         "visible": true, "image": "", "nbr_of_success": null, "users_url": ""},
+        {"id": 4, "name": "Bonus Hunter", "description": "", "tier": "none", "kind": "project",
+         "visible": true, "image": "", "nbr_of_success": null, "users_url": ""},
+        {"id": 45, "name": "Film buff", "description": "", "tier": "none", "kind": "project",
+         "visible": true, "image": "", "nbr_of_success": null, "users_url": ""}
+    ]"#;
+    let owned = r#"[
+        {"id": 4, "name": "Bonus Hunter", "description": "", "tier": "none", "kind": "project",
+         "visible": true, "image": "", "nbr_of_success": null, "users_url": ""}
+    ]"#;
+    let catalog: Vec<Achievement> = serde_json::from_str(catalog).unwrap();
+    let owned: Vec<Achievement> = serde_json::from_str(owned).unwrap();
+
+    let diff = AchievementsDiff::new(&owned, catalog);
+    let ids =
+        |achievements: &[Achievement]| -> Vec<i64> { achievements.iter().map(|a| a.id).collect() };
+    assert_eq!(ids(&diff.obtained), [4]);
+    assert_eq!(ids(&diff.missing), [1, 45]);
+}
+
+#[test]
+fn achievement_unknown_tier_test() {
+    let json = r#"{"id": 218, "name": "Code Explorer", "description": "", "tier": "challenge",
+        "kind": "hidden", "visible": true, "image": "/uploads/achievement/image/218.svg",
+        "nbr_of_success": null, "users_url": "https://api.intra.42.fr/v2/achievements/218/users"}"#;
+    let achievement: Achievement = serde_json::from_str(json).unwrap();
+    assert!(matches!(achievement.tier, Tier::Unknown));
+    assert!(matches!(achievement.kind, Kind::Unknown));
+}
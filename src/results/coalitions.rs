@@ -0,0 +1,165 @@
+use crate::paginate::Paginator;
+use crate::{Session, SessionError};
+use futures::TryStreamExt;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Coalition {
+    #[serde(rename = "id")]
+    pub id: i64,
+
+    #[serde(rename = "name")]
+    pub name: String,
+
+    #[serde(rename = "slug")]
+    pub slug: String,
+
+    #[serde(rename = "image_url")]
+    pub image_url: Option<String>,
+
+    #[serde(rename = "cover_url")]
+    pub cover_url: Option<String>,
+
+    #[serde(rename = "color")]
+    pub color: Option<String>,
+
+    #[serde(rename = "score")]
+    pub score: i64,
+
+    /// Id of the coalition master.
+    #[serde(rename = "user_id")]
+    pub user_id: Option<i64>,
+}
+
+/// Group of coalitions competing against each other on a campus and cursus.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Bloc {
+    #[serde(rename = "id")]
+    pub id: i64,
+
+    #[serde(rename = "campus_id")]
+    pub campus_id: i64,
+
+    #[serde(rename = "cursus_id")]
+    pub cursus_id: i64,
+
+    #[serde(rename = "squad_size")]
+    pub squad_size: Option<i64>,
+
+    #[serde(rename = "created_at")]
+    pub created_at: String,
+
+    #[serde(rename = "updated_at")]
+    pub updated_at: String,
+
+    #[serde(rename = "coalitions")]
+    pub coalitions: Vec<Coalition>,
+}
+
+/// Membership of a user in a coalition.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CoalitionsUser {
+    #[serde(rename = "id")]
+    pub id: i64,
+
+    #[serde(rename = "coalition_id")]
+    pub coalition_id: i64,
+
+    #[serde(rename = "user_id")]
+    pub user_id: i64,
+
+    #[serde(rename = "score")]
+    pub score: i64,
+
+    #[serde(rename = "rank")]
+    pub rank: Option<i64>,
+
+    #[serde(rename = "created_at")]
+    pub created_at: String,
+
+    #[serde(rename = "updated_at")]
+    pub updated_at: String,
+}
+
+/// Points given to, or taken from, a coalition.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Score {
+    #[serde(rename = "id")]
+    pub id: i64,
+
+    #[serde(rename = "coalition_id")]
+    pub coalition_id: i64,
+
+    #[serde(rename = "scoreable_id")]
+    pub scoreable_id: Option<i64>,
+
+    #[serde(rename = "scoreable_type")]
+    pub scoreable_type: Option<String>,
+
+    #[serde(rename = "coalitions_user_id")]
+    pub coalitions_user_id: Option<i64>,
+
+    #[serde(rename = "calculation_id")]
+    pub calculation_id: Option<i64>,
+
+    #[serde(rename = "value")]
+    pub value: i64,
+
+    #[serde(rename = "reason")]
+    pub reason: Option<String>,
+
+    #[serde(rename = "created_at")]
+    pub created_at: String,
+
+    #[serde(rename = "updated_at")]
+    pub updated_at: String,
+}
+
+/// Sorts coalitions by score, best first.
+pub fn standings(mut coalitions: Vec<Coalition>) -> Vec<Coalition> {
+    coalitions.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.name.cmp(&b.name)));
+    coalitions
+}
+
+impl Session {
+    /// `/v2/coalitions`
+    pub fn coalitions(&mut self) -> Paginator<'_, Coalition> {
+        self.paginate("v2/coalitions")
+    }
+    /// `/v2/blocs`
+    pub fn blocs(&mut self) -> Paginator<'_, Bloc> {
+        self.paginate("v2/blocs")
+    }
+    /// `/v2/blocs/:id/coalitions`
+    pub fn bloc_coalitions(&mut self, bloc_id: i64) -> Paginator<'_, Coalition> {
+        self.paginate(&format!("v2/blocs/{}/coalitions", bloc_id))
+    }
+    /// `/v2/coalitions/:id/coalitions_users`
+    pub fn coalition_users(&mut self, coalition_id: i64) -> Paginator<'_, CoalitionsUser> {
+        self.paginate(&format!("v2/coalitions/{}/coalitions_users", coalition_id))
+    }
+    /// `/v2/coalitions/:id/scores`
+    pub fn coalition_scores(&mut self, coalition_id: i64) -> Paginator<'_, Score> {
+        self.paginate(&format!("v2/coalitions/{}/scores", coalition_id))
+    }
+    /// Coalitions of a bloc ranked by score.
+    pub async fn bloc_standings(&mut self, bloc_id: i64) -> Result<Vec<Coalition>, SessionError> {
+        let coalitions = self.bloc_coalitions(bloc_id).try_collect().await?;
+        Ok(standings(coalitions))
+    }
+}
+
+#[test]
+fn coalition_standings_test() {
+    let json = r##"[
+        {"id": 85, "name": "Gun", "slug": "gun", "image_url": null, "cover_url": null,
+         "color": "#ff6950", "score": 3200, "user_id": 12},
+        {"id": 86, "name": "Gon", "slug": "gon", "image_url": null, "cover_url": null,
+         "color": "#4089ff", "score": 5120, "user_id": null},
+        {"id": 87, "name": "Gam", "slug": "gam", "image_url": null, "cover_url": null,
+         "color": "#3db065", "score": 3200, "user_id": 40}
+    ]"##;
+    let coalitions: Vec<Coalition> = serde_json::from_str(json).unwrap();
+    let ranked: Vec<String> = standings(coalitions).into_iter().map(|c| c.name).collect();
+    assert_eq!(ranked, ["Gon", "Gam", "Gun"]);
+}
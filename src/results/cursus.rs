@@ -0,0 +1,160 @@
+use crate::paginate::{with_params, Paginator};
+use crate::{Session, SessionError};
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use std::fmt;
+
+pub use crate::results::me::{Cursus, CursusUser};
+
+impl CursusUser {
+    /// Whether the user's blackhole date has passed, users without one never are.
+    pub fn is_blackholed(&self, now: DateTime<Utc>) -> bool {
+        self.blackholed_at
+            .as_deref()
+            .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+            .is_some_and(|at| at <= now)
+    }
+}
+
+/// Selects which cursus users make it into a leaderboard.
+#[derive(Debug, Default, Clone)]
+pub struct LeaderboardFilter {
+    pool_year: Option<String>,
+    pool_month: Option<String>,
+    blackholed: Option<bool>,
+}
+
+impl LeaderboardFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn pool_year<T: fmt::Display>(mut self, year: T) -> Self {
+        self.pool_year = Some(year.to_string());
+        self
+    }
+    /// Pool month as spelled by the API, e.g. `"july"`.
+    pub fn pool_month<T: fmt::Display>(mut self, month: T) -> Self {
+        self.pool_month = Some(month.to_string().to_lowercase());
+        self
+    }
+    /// Keep only blackholed users with `true`, only active ones with `false`.
+    pub fn blackholed(mut self, blackholed: bool) -> Self {
+        self.blackholed = Some(blackholed);
+        self
+    }
+    pub fn matches(&self, cursus_user: &CursusUser, now: DateTime<Utc>) -> bool {
+        let user = &cursus_user.user;
+        let same = |wanted: &Option<String>, value: &Option<String>| {
+            wanted.is_none() || wanted.as_deref() == value.as_deref()
+        };
+        same(&self.pool_year, &user.pool_year)
+            && same(&self.pool_month, &user.pool_month)
+            && self
+                .blackholed
+                .is_none_or(|blackholed| cursus_user.is_blackholed(now) == blackholed)
+    }
+}
+
+/// Keeps the cursus users matching `filter` and sorts them by level, best first.
+pub fn leaderboard(
+    cursus_users: Vec<CursusUser>,
+    filter: &LeaderboardFilter,
+    now: DateTime<Utc>,
+) -> Vec<CursusUser> {
+    let mut ranked: Vec<CursusUser> = cursus_users
+        .into_iter()
+        .filter(|cursus_user| filter.matches(cursus_user, now))
+        .collect();
+    ranked.sort_by(|a, b| {
+        b.level
+            .total_cmp(&a.level)
+            .then_with(|| a.user.login.cmp(&b.user.login))
+    });
+    ranked
+}
+
+impl Session {
+    /// `/v2/cursus`
+    pub fn cursus(&mut self) -> Paginator<'_, Cursus> {
+        self.paginate("v2/cursus")
+    }
+    /// `/v2/cursus/:id/cursus_users`
+    pub fn cursus_cursus_users(&mut self, cursus_id: i64) -> Paginator<'_, CursusUser> {
+        self.paginate(&format!("v2/cursus/{}/cursus_users", cursus_id))
+    }
+    /// `/v2/campus/:id/cursus_users`
+    pub fn campus_cursus_users(&mut self, campus_id: i64) -> Paginator<'_, CursusUser> {
+        self.paginate(&format!("v2/campus/{}/cursus_users", campus_id))
+    }
+    /// Users of a campus in a cursus ranked by level.
+    pub async fn campus_leaderboard(
+        &mut self,
+        campus_id: i64,
+        cursus_id: i64,
+        filter: &LeaderboardFilter,
+    ) -> Result<Vec<CursusUser>, SessionError> {
+        let path = with_params(
+            &format!("v2/campus/{}/cursus_users", campus_id),
+            &[("filter[cursus_id]".to_string(), cursus_id.to_string())],
+        );
+        let cursus_users = self.paginate(&path).try_collect().await?;
+        Ok(leaderboard(cursus_users, filter, Utc::now()))
+    }
+}
+
+#[test]
+fn leaderboard_test() {
+    use chrono::TimeZone;
+
+    let json = r#"[
+        {"grade": null, "level": 7.5, "skills": [], "blackholed_at": "2022-01-01T00:00:00.000Z", "id": 1,
+         "begin_at": "2021-10-04T08:00:00.000Z", "end_at": null, "cursus_id": 21,
+         "has_coalition": true, "created_at": "2021-09-20T10:00:00.000Z",
+         "updated_at": "2022-03-01T10:00:00.000Z",
+         "cursus": {"id": 21, "created_at": null, "name": "42cursus", "slug": "42cursus", "parent_id": null},
+         "user": {"id": 1, "email": "", "login": "amy", "first_name": "", "last_name": "",
+                  "usual_full_name": "", "usual_first_name": null, "url": "", "phone": "hidden",
+                  "displayname": "", "staff?": false, "correction_point": 3, "wallet": 0,
+                  "pool_month": "august", "pool_year": "2021", "location": null,
+                  "anonymize_date": "", "created_at": "", "updated_at": ""}},
+        {"grade": null, "level": 9.1, "skills": [], "blackholed_at": "2023-01-01T00:00:00.000Z", "id": 1,
+         "begin_at": "2021-10-04T08:00:00.000Z", "end_at": null, "cursus_id": 21,
+         "has_coalition": true, "created_at": "2021-09-20T10:00:00.000Z",
+         "updated_at": "2022-03-01T10:00:00.000Z",
+         "cursus": {"id": 21, "created_at": null, "name": "42cursus", "slug": "42cursus", "parent_id": null},
+         "user": {"id": 1, "email": "", "login": "bob", "first_name": "", "last_name": "",
+                  "usual_full_name": "", "usual_first_name": null, "url": "", "phone": "hidden",
+                  "displayname": "", "staff?": false, "correction_point": 3, "wallet": 0,
+                  "pool_month": "august", "pool_year": "2021", "location": null,
+                  "anonymize_date": "", "created_at": "", "updated_at": ""}},
+        {"grade": null, "level": 12.0, "skills": [], "blackholed_at": null, "id": 1,
+         "begin_at": "2021-10-04T08:00:00.000Z", "end_at": null, "cursus_id": 21,
+         "has_coalition": true, "created_at": "2021-09-20T10:00:00.000Z",
+         "updated_at": "2022-03-01T10:00:00.000Z",
+         "cursus": {"id": 21, "created_at": null, "name": "42cursus", "slug": "42cursus", "parent_id": null},
+         "user": {"id": 1, "email": "", "login": "cal", "first_name": "", "last_name": "",
+                  "usual_full_name": "", "usual_first_name": null, "url": "", "phone": "hidden",
+                  "displayname": "", "staff?": false, "correction_point": 3, "wallet": 0,
+                  "pool_month": "august", "pool_year": "2020", "location": null,
+                  "anonymize_date": "", "created_at": "", "updated_at": ""}},
+        {"grade": null, "level": 9.1, "skills": [], "blackholed_at": null, "id": 1,
+         "begin_at": "2021-10-04T08:00:00.000Z", "end_at": null, "cursus_id": 21,
+         "has_coalition": true, "created_at": "2021-09-20T10:00:00.000Z",
+         "updated_at": "2022-03-01T10:00:00.000Z",
+         "cursus": {"id": 21, "created_at": null, "name": "42cursus", "slug": "42cursus", "parent_id": null},
+         "user": {"id": 1, "email": "", "login": "dan", "first_name": "", "last_name": "",
+                  "usual_full_name": "", "usual_first_name": null, "url": "", "phone": "hidden",
+                  "displayname": "", "staff?": false, "correction_point": 3, "wallet": 0,
+                  "pool_month": "august", "pool_year": "2021", "location": null,
+                  "anonymize_date": "", "created_at": "", "updated_at": ""}}
+    ]"#;
+    let cursus_users: Vec<CursusUser> = serde_json::from_str(json).unwrap();
+    let now = Utc.with_ymd_and_hms(2022, 3, 2, 0, 0, 0).unwrap();
+
+    let filter = LeaderboardFilter::new().pool_year(2021).blackholed(false);
+    let ranked: Vec<String> = leaderboard(cursus_users, &filter, now)
+        .into_iter()
+        .map(|c| c.user.login)
+        .collect();
+    assert_eq!(ranked, ["bob", "dan"]);
+}
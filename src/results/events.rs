@@ -0,0 +1,137 @@
+use crate::paginate::Paginator;
+use crate::results::users::UserRef;
+use crate::{Session, SessionError};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Event {
+    #[serde(rename = "id")]
+    pub id: i64,
+
+    #[serde(rename = "name")]
+    pub name: String,
+
+    #[serde(rename = "description")]
+    pub description: Option<String>,
+
+    #[serde(rename = "location")]
+    pub location: Option<String>,
+
+    /// e.g. `conference`, `workshop`, `hackathon` or `rush`.
+    #[serde(rename = "kind")]
+    pub kind: String,
+
+    #[serde(rename = "max_people")]
+    pub max_people: Option<i64>,
+
+    #[serde(rename = "nbr_subscribers")]
+    pub nbr_subscribers: i64,
+
+    #[serde(rename = "begin_at")]
+    pub begin_at: DateTime<Utc>,
+
+    #[serde(rename = "end_at")]
+    pub end_at: DateTime<Utc>,
+
+    #[serde(rename = "campus_ids")]
+    pub campus_ids: Vec<i64>,
+
+    #[serde(rename = "cursus_ids")]
+    pub cursus_ids: Vec<i64>,
+
+    #[serde(rename = "created_at")]
+    pub created_at: DateTime<Utc>,
+
+    #[serde(rename = "updated_at")]
+    pub updated_at: DateTime<Utc>,
+
+    #[serde(rename = "prohibition_of_cancellation")]
+    pub prohibition_of_cancellation: Option<i64>,
+}
+
+impl Event {
+    /// Whether another user can still subscribe.
+    pub fn has_free_seats(&self) -> bool {
+        crate::results::has_free_seats(self.max_people, self.nbr_subscribers)
+    }
+}
+
+/// Subscription of a user to an event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventsUser {
+    #[serde(rename = "id")]
+    pub id: i64,
+
+    #[serde(rename = "event_id")]
+    pub event_id: i64,
+
+    #[serde(rename = "user_id")]
+    pub user_id: i64,
+
+    #[serde(rename = "user")]
+    pub user: Option<UserRef>,
+
+    #[serde(rename = "event")]
+    pub event: Option<Event>,
+}
+
+impl Session {
+    /// `/v2/events/:id`
+    pub async fn event(&mut self, event_id: i64) -> Result<Event, SessionError> {
+        self.get(&format!("v2/events/{}", event_id)).await
+    }
+    /// `/v2/campus/:id/events`
+    pub fn campus_events(&mut self, campus_id: i64) -> Paginator<'_, Event> {
+        self.paginate(&format!("v2/campus/{}/events", campus_id))
+    }
+    /// `/v2/cursus/:id/events`
+    pub fn cursus_events(&mut self, cursus_id: i64) -> Paginator<'_, Event> {
+        self.paginate(&format!("v2/cursus/{}/events", cursus_id))
+    }
+    /// `/v2/users/:id/events`: events the user subscribed to.
+    pub fn user_events<T: fmt::Display>(&mut self, login_or_id: T) -> Paginator<'_, Event> {
+        self.paginate(&format!("v2/users/{}/events", login_or_id))
+    }
+    /// `/v2/events/:id/events_users`: subscriptions to an event.
+    pub fn event_users(&mut self, event_id: i64) -> Paginator<'_, EventsUser> {
+        self.paginate(&format!("v2/events/{}/events_users", event_id))
+    }
+    /// Subscribes a user to an event with `POST /v2/events_users`.
+    ///
+    /// Subscribing someone else than the token owner needs a staff token,
+    /// the API answers `Forbidden` otherwise.
+    pub async fn subscribe_event(
+        &mut self,
+        event_id: i64,
+        user_id: i64,
+    ) -> Result<EventsUser, SessionError> {
+        let form = [
+            ("events_user[event_id]".to_string(), event_id.to_string()),
+            ("events_user[user_id]".to_string(), user_id.to_string()),
+        ];
+        self.post("v2/events_users", &form).await
+    }
+    /// Removes a subscription with `DELETE /v2/events_users/:id`.
+    pub async fn unsubscribe_event(&mut self, events_user_id: i64) -> Result<(), SessionError> {
+        self.delete(&format!("v2/events_users/{}", events_user_id))
+            .await
+    }
+}
+
+#[test]
+fn event_deserialize_test() {
+    let json = r#"{
+        "id": 8, "name": "Intro to Rust", "description": "Ownership and borrowing",
+        "location": "Cluster 1", "kind": "workshop", "max_people": 20, "nbr_subscribers": 20,
+        "begin_at": "2022-03-02T09:00:00.000Z", "end_at": "2022-03-02T11:00:00.000Z",
+        "campus_ids": [29], "cursus_ids": [21],
+        "created_at": "2022-02-20T10:00:00.000Z", "updated_at": "2022-02-21T10:00:00.000Z",
+        "prohibition_of_cancellation": null
+    }"#;
+    let event: Event = serde_json::from_str(json).unwrap();
+    assert_eq!(event.kind, "workshop");
+    assert_eq!(event.end_at - event.begin_at, chrono::Duration::hours(2));
+    assert!(!event.has_free_seats());
+}
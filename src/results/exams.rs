@@ -0,0 +1,144 @@
+use crate::paginate::Paginator;
+use crate::results::campus::CampusElement;
+use crate::results::me::Cursus;
+use crate::results::projects::ProjectRef;
+use crate::results::users::UserRef;
+use crate::{Session, SessionError};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Exam {
+    #[serde(rename = "id")]
+    pub id: i64,
+
+    #[serde(rename = "name")]
+    pub name: String,
+
+    #[serde(rename = "ip_range")]
+    pub ip_range: Option<String>,
+
+    #[serde(rename = "begin_at")]
+    pub begin_at: DateTime<Utc>,
+
+    #[serde(rename = "end_at")]
+    pub end_at: DateTime<Utc>,
+
+    #[serde(rename = "location")]
+    pub location: Option<String>,
+
+    #[serde(rename = "max_people")]
+    pub max_people: Option<i64>,
+
+    #[serde(rename = "nbr_subscribers")]
+    pub nbr_subscribers: i64,
+
+    #[serde(rename = "created_at")]
+    pub created_at: DateTime<Utc>,
+
+    #[serde(rename = "updated_at")]
+    pub updated_at: DateTime<Utc>,
+
+    #[serde(rename = "campus")]
+    pub campus: Option<CampusElement>,
+
+    #[serde(rename = "cursus")]
+    pub cursus: Vec<Cursus>,
+
+    #[serde(rename = "projects")]
+    pub projects: Vec<ProjectRef>,
+}
+
+impl Exam {
+    pub fn is_upcoming(&self, now: DateTime<Utc>) -> bool {
+        self.begin_at > now
+    }
+    /// Whether the exam room can take one more registration.
+    pub fn has_free_seats(&self) -> bool {
+        crate::results::has_free_seats(self.max_people, self.nbr_subscribers)
+    }
+}
+
+/// Registration of a user to an exam.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExamsUser {
+    #[serde(rename = "id")]
+    pub id: i64,
+
+    #[serde(rename = "exam_id")]
+    pub exam_id: i64,
+
+    #[serde(rename = "user_id")]
+    pub user_id: i64,
+
+    #[serde(rename = "created_at")]
+    pub created_at: DateTime<Utc>,
+
+    #[serde(rename = "user")]
+    pub user: Option<UserRef>,
+}
+
+impl Session {
+    /// `/v2/exams`
+    pub fn exams(&mut self) -> Paginator<'_, Exam> {
+        self.paginate("v2/exams")
+    }
+    /// `/v2/exams/:id`
+    pub async fn exam(&mut self, exam_id: i64) -> Result<Exam, SessionError> {
+        self.get(&format!("v2/exams/{}", exam_id)).await
+    }
+    /// `/v2/campus/:id/exams`
+    pub fn campus_exams(&mut self, campus_id: i64) -> Paginator<'_, Exam> {
+        self.paginate(&format!("v2/campus/{}/exams", campus_id))
+    }
+    /// `/v2/users/:id/exams`: exams the user registered to.
+    pub fn user_exams<T: fmt::Display>(&mut self, login_or_id: T) -> Paginator<'_, Exam> {
+        self.paginate(&format!("v2/users/{}/exams", login_or_id))
+    }
+    /// `/v2/exams/:id/exams_users`: registrations to an exam.
+    pub fn exam_users(&mut self, exam_id: i64) -> Paginator<'_, ExamsUser> {
+        self.paginate(&format!("v2/exams/{}/exams_users", exam_id))
+    }
+    /// Registers a user with `POST /v2/exams/:id/exams_users`.
+    pub async fn register_exam(
+        &mut self,
+        exam_id: i64,
+        user_id: i64,
+    ) -> Result<ExamsUser, SessionError> {
+        let form = [("exams_user[user_id]".to_string(), user_id.to_string())];
+        self.post(&format!("v2/exams/{}/exams_users", exam_id), &form)
+            .await
+    }
+    /// Cancels a registration with `DELETE /v2/exams/:id/exams_users/:id`.
+    pub async fn unregister_exam(
+        &mut self,
+        exam_id: i64,
+        exams_user_id: i64,
+    ) -> Result<(), SessionError> {
+        self.delete(&format!(
+            "v2/exams/{}/exams_users/{}",
+            exam_id, exams_user_id
+        ))
+        .await
+    }
+}
+
+#[test]
+fn exam_deserialize_test() {
+    use chrono::TimeZone;
+
+    let json = r#"{
+        "id": 5, "name": "Exam Rank 02", "ip_range": "10.11.0.0/16",
+        "begin_at": "2022-03-04T07:00:00.000Z", "end_at": "2022-03-04T10:00:00.000Z",
+        "location": "Cluster 2", "max_people": 40, "nbr_subscribers": 12,
+        "created_at": "2022-02-20T10:00:00.000Z", "updated_at": "2022-02-21T10:00:00.000Z",
+        "campus": null, "cursus": [],
+        "projects": [{"id": 1320, "name": "Exam Rank 02", "slug": "exam-rank-02", "parent_id": null}]
+    }"#;
+    let exam: Exam = serde_json::from_str(json).unwrap();
+    assert_eq!(exam.projects[0].slug, "exam-rank-02");
+    assert!(exam.has_free_seats());
+    assert!(exam.is_upcoming(Utc.with_ymd_and_hms(2022, 3, 1, 0, 0, 0).unwrap()));
+    assert!(!exam.is_upcoming(Utc.with_ymd_and_hms(2022, 3, 5, 0, 0, 0).unwrap()));
+}
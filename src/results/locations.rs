@@ -0,0 +1,238 @@
+use crate::paginate::{with_params, Paginator};
+use crate::results::users::UserRef;
+use crate::{Session, SessionError};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, SecondsFormat, TimeZone, Utc};
+use futures::TryStreamExt;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A session of a user on a campus computer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Location {
+    #[serde(rename = "id")]
+    pub id: i64,
+
+    #[serde(rename = "begin_at")]
+    pub begin_at: DateTime<Utc>,
+
+    /// `None` while the user is still logged in.
+    #[serde(rename = "end_at")]
+    pub end_at: Option<DateTime<Utc>>,
+
+    #[serde(rename = "primary")]
+    pub primary: bool,
+
+    #[serde(rename = "floor")]
+    pub floor: Option<String>,
+
+    #[serde(rename = "row")]
+    pub row: Option<String>,
+
+    #[serde(rename = "post")]
+    pub post: Option<String>,
+
+    /// Computer name, e.g. `c1r2s3`.
+    #[serde(rename = "host")]
+    pub host: String,
+
+    #[serde(rename = "campus_id")]
+    pub campus_id: i64,
+
+    #[serde(rename = "user")]
+    pub user: UserRef,
+}
+
+/// Time spent logged in, per local day.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Logtime {
+    pub per_day: BTreeMap<NaiveDate, Duration>,
+}
+
+impl Logtime {
+    /// Sums `locations` clipped to `[from, to)`, splitting them at midnight in `tz`.
+    ///
+    /// Sessions still open count up to now.
+    pub fn from_locations<Tz: TimeZone>(
+        locations: &[Location],
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        tz: &Tz,
+    ) -> Self {
+        let now = Utc::now();
+        let mut per_day = BTreeMap::new();
+        for location in locations {
+            let mut start = location.begin_at.max(from);
+            let end = location.end_at.unwrap_or(now).min(to);
+            while start < end {
+                let day = start.with_timezone(tz).date_naive();
+                let next_day = day.succ_opt().and_then(|d| d.and_hms_opt(0, 0, 0));
+                let midnight = next_day
+                    .and_then(|d| tz.from_local_datetime(&d).earliest())
+                    .map(|d| d.with_timezone(&Utc))
+                    .unwrap_or(start + Duration::days(1));
+                let chunk_end = end.min(midnight);
+                *per_day.entry(day).or_insert_with(Duration::zero) += chunk_end - start;
+                start = chunk_end;
+            }
+        }
+        Logtime { per_day }
+    }
+    /// Logtime per week, keyed by the monday starting it.
+    pub fn per_week(&self) -> BTreeMap<NaiveDate, Duration> {
+        let mut per_week = BTreeMap::new();
+        for (day, logtime) in &self.per_day {
+            let monday = *day - Duration::days(day.weekday().num_days_from_monday() as i64);
+            *per_week.entry(monday).or_insert_with(Duration::zero) += *logtime;
+        }
+        per_week
+    }
+    pub fn total(&self) -> Duration {
+        self.per_day
+            .values()
+            .fold(Duration::zero(), |acc, d| acc + *d)
+    }
+}
+
+impl Session {
+    /// `/v2/locations`
+    pub fn locations(&mut self) -> Paginator<'_, Location> {
+        self.paginate("v2/locations")
+    }
+    /// `/v2/campus/:id/locations`
+    pub fn campus_locations(&mut self, campus_id: i64) -> Paginator<'_, Location> {
+        self.paginate(&format!("v2/campus/{}/locations", campus_id))
+    }
+    /// `/v2/campus/:id/locations` of the users logged in right now.
+    pub fn campus_active_locations(&mut self, campus_id: i64) -> Paginator<'_, Location> {
+        self.paginate(&format!(
+            "v2/campus/{}/locations?filter[active]=true",
+            campus_id
+        ))
+    }
+    /// `/v2/users/:id/locations`
+    pub fn user_locations<T: fmt::Display>(&mut self, login_or_id: T) -> Paginator<'_, Location> {
+        self.paginate(&format!("v2/users/{}/locations", login_or_id))
+    }
+    /// Logtime of a user between `from` and `to`, with days cut at midnight in `tz`.
+    pub async fn user_logtime<T: fmt::Display, Tz: TimeZone>(
+        &mut self,
+        login_or_id: T,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        tz: &Tz,
+    ) -> Result<Logtime, SessionError> {
+        let path = format!("v2/users/{}/locations", login_or_id);
+        let mut locations = BTreeMap::new();
+        for query in overlap_queries(&path, from, to) {
+            let page: Vec<Location> = self.paginate(&query).try_collect().await?;
+            locations.extend(page.into_iter().map(|l| (l.id, l)));
+        }
+        let locations: Vec<Location> = locations.into_values().collect();
+        Ok(Logtime::from_locations(&locations, from, to, tz))
+    }
+}
+
+/// Queries of `path` returning every location overlapping `from`..`to`.
+///
+/// Closed locations are matched on both bounds, open ones have no `end_at`
+/// to range on and are fetched with `filter[active]`.
+fn overlap_queries(path: &str, from: DateTime<Utc>, to: DateTime<Utc>) -> [String; 2] {
+    let at = |d: DateTime<Utc>| d.to_rfc3339_opts(SecondsFormat::Secs, true);
+    let far_future = Utc.with_ymd_and_hms(9999, 12, 31, 23, 59, 59).unwrap();
+    let closed = with_params(
+        path,
+        &[
+            (
+                "range[begin_at]".to_string(),
+                format!("{},{}", at(DateTime::UNIX_EPOCH), at(to)),
+            ),
+            (
+                "range[end_at]".to_string(),
+                format!("{},{}", at(from), at(far_future)),
+            ),
+        ],
+    );
+    let open = with_params(path, &[("filter[active]".to_string(), "true".to_string())]);
+    [closed, open]
+}
+
+#[test]
+fn location_deserialize_test() {
+    let json = r#"{
+        "id": 1, "begin_at": "2022-01-10T08:30:00.000Z", "end_at": null, "primary": true,
+        "floor": null, "row": null, "post": null, "host": "c1r2s3", "campus_id": 29,
+        "user": {"id": 7, "login": "jpark", "url": "https://api.intra.42.fr/v2/users/jpark"}
+    }"#;
+    let location: Location = serde_json::from_str(json).unwrap();
+    assert_eq!(location.host, "c1r2s3");
+    assert_eq!(
+        location.begin_at,
+        Utc.with_ymd_and_hms(2022, 1, 10, 8, 30, 0).unwrap()
+    );
+    assert!(location.end_at.is_none());
+}
+
+#[test]
+fn overlap_queries_test() {
+    let from = Utc.with_ymd_and_hms(2021, 7, 1, 0, 0, 0).unwrap();
+    let to = Utc.with_ymd_and_hms(2021, 8, 1, 0, 0, 0).unwrap();
+    let [closed, open] = overlap_queries("v2/users/jpark/locations", from, to);
+    assert_eq!(
+        closed,
+        "v2/users/jpark/locations\
+         ?range%5Bbegin_at%5D=1970-01-01T00%3A00%3A00Z%2C2021-08-01T00%3A00%3A00Z\
+         &range%5Bend_at%5D=2021-07-01T00%3A00%3A00Z%2C9999-12-31T23%3A59%3A59Z"
+    );
+    assert_eq!(open, "v2/users/jpark/locations?filter%5Bactive%5D=true");
+}
+
+#[test]
+fn logtime_test() {
+    use chrono::FixedOffset;
+
+    let at = |d, h| Utc.with_ymd_and_hms(2022, 1, d, h, 0, 0).unwrap();
+    let location = |begin, end| Location {
+        id: 0,
+        begin_at: begin,
+        end_at: Some(end),
+        primary: true,
+        floor: None,
+        row: None,
+        post: None,
+        host: "c1r2s3".to_string(),
+        campus_id: 29,
+        user: UserRef::default(),
+    };
+    // Sunday 20:00 to Monday 02:00 UTC, then Monday 10:00 to 12:00 UTC.
+    let locations = [
+        location(at(9, 20), at(10, 2)),
+        location(at(10, 10), at(10, 12)),
+    ];
+    let day = |d| NaiveDate::from_ymd_opt(2022, 1, d).unwrap();
+
+    let logtime = Logtime::from_locations(&locations, at(1, 0), at(31, 0), &Utc);
+    assert_eq!(logtime.per_day[&day(9)], Duration::hours(4));
+    assert_eq!(logtime.per_day[&day(10)], Duration::hours(4));
+    assert_eq!(logtime.per_week()[&day(3)], Duration::hours(4));
+    assert_eq!(logtime.per_week()[&day(10)], Duration::hours(4));
+    assert_eq!(logtime.total(), Duration::hours(8));
+
+    // In Seoul (UTC+9) everything happens on Monday.
+    let seoul = FixedOffset::east_opt(9 * 3600).unwrap();
+    let logtime = Logtime::from_locations(&locations, at(1, 0), at(31, 0), &seoul);
+    assert_eq!(logtime.per_day.len(), 1);
+    assert_eq!(logtime.per_day[&day(10)], Duration::hours(8));
+
+    // Clipped to the requested range.
+    let logtime = Logtime::from_locations(&locations, at(10, 0), at(10, 11), &Utc);
+    assert_eq!(logtime.total(), Duration::hours(3));
+
+    // Still logged in since the evening before the range.
+    let open = Location {
+        end_at: None,
+        ..location(at(9, 20), at(9, 20))
+    };
+    let logtime = Logtime::from_locations(&[open], at(10, 0), at(10, 6), &Utc);
+    assert_eq!(logtime.per_day[&day(10)], Duration::hours(6));
+}
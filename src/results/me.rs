@@ -1,4 +1,5 @@
 extern crate serde_json;
+pub use crate::results::projects::{ProjectsUser, Status};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Default, Serialize, Deserialize)]
@@ -13,223 +14,223 @@ pub struct Me {
     pub login: String,
 
     #[serde(rename = "first_name")]
-    first_name: String,
+    pub first_name: String,
 
     #[serde(rename = "last_name")]
-    last_name: String,
+    pub last_name: String,
 
     #[serde(rename = "usual_full_name")]
-    usual_full_name: String,
+    pub usual_full_name: String,
 
     #[serde(rename = "usual_first_name")]
-    usual_first_name: Option<serde_json::Value>,
+    pub usual_first_name: Option<serde_json::Value>,
 
     #[serde(rename = "url")]
-    url: String,
+    pub url: String,
 
     #[serde(rename = "phone")]
-    phone: String,
+    pub phone: String,
 
     #[serde(rename = "displayname")]
     pub displayname: String,
 
     #[serde(rename = "image_url")]
-    image_url: String,
+    pub image_url: String,
 
     #[serde(rename = "staff?")]
-    staff: bool,
+    pub staff: bool,
 
     #[serde(rename = "correction_point")]
     pub correction_point: i64,
 
     #[serde(rename = "pool_month")]
-    pool_month: String,
+    pub pool_month: String,
 
     #[serde(rename = "pool_year")]
-    pool_year: String,
+    pub pool_year: String,
 
     #[serde(rename = "location")]
-    location: Option<serde_json::Value>,
+    pub location: Option<String>,
 
     #[serde(rename = "wallet")]
     pub wallet: i64,
 
     #[serde(rename = "anonymize_date")]
-    anonymize_date: String,
+    pub anonymize_date: String,
 
     #[serde(rename = "created_at")]
-    created_at: String,
+    pub created_at: String,
 
     #[serde(rename = "updated_at")]
-    updated_at: String,
+    pub updated_at: String,
 
     #[serde(rename = "alumni")]
-    alumni: bool,
+    pub alumni: bool,
 
     #[serde(rename = "is_launched?")]
-    is_launched: bool,
+    pub is_launched: bool,
 
     #[serde(rename = "groups")]
-    groups: Vec<Option<serde_json::Value>>,
+    pub groups: Vec<Option<serde_json::Value>>,
 
     #[serde(rename = "cursus_users")]
     pub cursus_users: Vec<CursusUser>,
 
     #[serde(rename = "projects_users")]
-    projects_users: Vec<ProjectsUser>,
+    pub projects_users: Vec<ProjectsUser>,
 
     #[serde(rename = "languages_users")]
-    languages_users: Vec<LanguagesUser>,
+    pub languages_users: Vec<LanguagesUser>,
 
     #[serde(rename = "achievements")]
-    achievements: Vec<Achievement>,
+    pub achievements: Vec<Achievement>,
 
     #[serde(rename = "titles")]
     pub titles: Vec<Title>,
 
     #[serde(rename = "titles_users")]
-    titles_users: Vec<TitlesUser>,
+    pub titles_users: Vec<TitlesUser>,
 
     #[serde(rename = "partnerships")]
-    partnerships: Vec<Option<serde_json::Value>>,
+    pub partnerships: Vec<Option<serde_json::Value>>,
 
     #[serde(rename = "patroned")]
-    patroned: Vec<Option<serde_json::Value>>,
+    pub patroned: Vec<Option<serde_json::Value>>,
 
     #[serde(rename = "patroning")]
-    patroning: Vec<Option<serde_json::Value>>,
+    pub patroning: Vec<Option<serde_json::Value>>,
 
     #[serde(rename = "expertises_users")]
-    expertises_users: Vec<ExpertisesUser>,
+    pub expertises_users: Vec<ExpertisesUser>,
 
     #[serde(rename = "roles")]
-    roles: Vec<Option<serde_json::Value>>,
+    pub roles: Vec<Option<serde_json::Value>>,
 
     #[serde(rename = "campus")]
-    campus: Vec<Campus>,
+    pub campus: Vec<Campus>,
 
     #[serde(rename = "campus_users")]
-    campus_users: Vec<CampusUser>,
+    pub campus_users: Vec<CampusUser>,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct Achievement {
     #[serde(rename = "id")]
-    id: i64,
+    pub id: i64,
 
     #[serde(rename = "name")]
-    name: String,
+    pub name: String,
 
     #[serde(rename = "description")]
-    description: String,
+    pub description: String,
 
     #[serde(rename = "tier")]
-    tier: Tier,
+    pub tier: Tier,
 
     #[serde(rename = "kind")]
-    kind: Kind,
+    pub kind: Kind,
 
     #[serde(rename = "visible")]
-    visible: bool,
+    pub visible: bool,
 
     #[serde(rename = "image")]
-    image: String,
+    pub image: String,
 
     #[serde(rename = "nbr_of_success")]
-    nbr_of_success: Option<i64>,
+    pub nbr_of_success: Option<i64>,
 
     #[serde(rename = "users_url")]
-    users_url: String,
+    pub users_url: String,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct Campus {
     #[serde(rename = "id")]
-    id: i64,
+    pub id: i64,
 
     #[serde(rename = "name")]
-    name: String,
+    pub name: String,
 
     #[serde(rename = "time_zone")]
-    time_zone: String,
+    pub time_zone: String,
 
     #[serde(rename = "language")]
-    language: Language,
+    pub language: Language,
 
     #[serde(rename = "users_count")]
-    users_count: i64,
+    pub users_count: i64,
 
     #[serde(rename = "vogsphere_id")]
-    vogsphere_id: i64,
+    pub vogsphere_id: i64,
 
     #[serde(rename = "country")]
-    country: String,
+    pub country: String,
 
     #[serde(rename = "address")]
-    address: String,
+    pub address: String,
 
     #[serde(rename = "zip")]
-    zip: String,
+    pub zip: String,
 
     #[serde(rename = "city")]
-    city: String,
+    pub city: String,
 
     #[serde(rename = "website")]
-    website: String,
+    pub website: String,
 
     #[serde(rename = "facebook")]
-    facebook: String,
+    pub facebook: String,
 
     #[serde(rename = "twitter")]
-    twitter: String,
+    pub twitter: String,
 
     #[serde(rename = "active")]
-    active: bool,
+    pub active: bool,
 
     #[serde(rename = "email_extension")]
-    email_extension: String,
+    pub email_extension: String,
 
     #[serde(rename = "default_hidden_phone")]
-    default_hidden_phone: bool,
+    pub default_hidden_phone: bool,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct Language {
     #[serde(rename = "id")]
-    id: i64,
+    pub id: i64,
 
     #[serde(rename = "name")]
-    name: String,
+    pub name: String,
 
     #[serde(rename = "identifier")]
-    identifier: String,
+    pub identifier: String,
 
     #[serde(rename = "created_at")]
-    created_at: String,
+    pub created_at: String,
 
     #[serde(rename = "updated_at")]
-    updated_at: String,
+    pub updated_at: String,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct CampusUser {
     #[serde(rename = "id")]
-    id: i64,
+    pub id: i64,
 
     #[serde(rename = "user_id")]
-    user_id: i64,
+    pub user_id: i64,
 
     #[serde(rename = "campus_id")]
-    campus_id: i64,
+    pub campus_id: i64,
 
     #[serde(rename = "is_primary")]
-    is_primary: bool,
+    pub is_primary: bool,
 
     #[serde(rename = "created_at")]
-    created_at: String,
+    pub created_at: String,
 
     #[serde(rename = "updated_at")]
-    updated_at: String,
+    pub updated_at: String,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize)]
@@ -295,7 +296,7 @@ pub struct Cursus {
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct Skill {
     #[serde(rename = "id")]
-    id: i64,
+    pub id: i64,
 
     #[serde(rename = "name")]
     pub name: String,
@@ -336,7 +337,7 @@ pub struct User {
     #[serde(rename = "displayname")]
     pub displayname: String,
 
-    #[serde(rename = "image_url")]
+    #[serde(rename = "image_url", default)]
     pub image_url: String,
 
     #[serde(rename = "staff?")]
@@ -346,13 +347,13 @@ pub struct User {
     pub correction_point: i64,
 
     #[serde(rename = "pool_month")]
-    pub pool_month: String,
+    pub pool_month: Option<String>,
 
     #[serde(rename = "pool_year")]
-    pub pool_year: String,
+    pub pool_year: Option<String>,
 
     #[serde(rename = "location")]
-    pub location: Option<serde_json::Value>,
+    pub location: Option<String>,
 
     #[serde(rename = "wallet")]
     pub wallet: i64,
@@ -366,101 +367,59 @@ pub struct User {
     #[serde(rename = "updated_at")]
     pub updated_at: String,
 
-    #[serde(rename = "alumni")]
+    #[serde(rename = "alumni", default)]
     pub alumni: bool,
 
-    #[serde(rename = "is_launched?")]
+    #[serde(rename = "is_launched?", default)]
     pub is_launched: bool,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct ExpertisesUser {
     #[serde(rename = "id")]
-    id: i64,
+    pub id: i64,
 
     #[serde(rename = "expertise_id")]
-    expertise_id: i64,
+    pub expertise_id: i64,
 
     #[serde(rename = "interested")]
-    interested: bool,
+    pub interested: bool,
 
     #[serde(rename = "value")]
-    value: i64,
+    pub value: i64,
 
     #[serde(rename = "contact_me")]
-    contact_me: bool,
+    pub contact_me: bool,
 
     #[serde(rename = "created_at")]
-    created_at: String,
+    pub created_at: String,
 
     #[serde(rename = "user_id")]
-    user_id: i64,
+    pub user_id: i64,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct LanguagesUser {
     #[serde(rename = "id")]
-    id: i64,
+    pub id: i64,
 
     #[serde(rename = "language_id")]
-    language_id: i64,
+    pub language_id: i64,
 
     #[serde(rename = "user_id")]
-    user_id: i64,
+    pub user_id: i64,
 
     #[serde(rename = "position")]
-    position: i64,
+    pub position: i64,
 
     #[serde(rename = "created_at")]
-    created_at: String,
-}
-
-#[derive(Debug, Default, Serialize, Deserialize)]
-pub struct ProjectsUser {
-    #[serde(rename = "id")]
-    id: i64,
-
-    #[serde(rename = "occurrence")]
-    occurrence: i64,
-
-    #[serde(rename = "final_mark")]
-    final_mark: Option<i64>,
-
-    #[serde(rename = "status")]
-    status: Status,
-
-    #[serde(rename = "validated?")]
-    validated: Option<bool>,
-
-    #[serde(rename = "current_team_id")]
-    current_team_id: i64,
-
-    #[serde(rename = "project")]
-    project: Cursus,
-
-    #[serde(rename = "cursus_ids")]
-    cursus_ids: Vec<i64>,
-
-    #[serde(rename = "marked_at")]
-    marked_at: Option<String>,
-
-    #[serde(rename = "marked")]
-    marked: bool,
-
-    #[serde(rename = "retriable_at")]
-    retriable_at: Option<String>,
-
-    #[serde(rename = "created_at")]
-    created_at: String,
-
-    #[serde(rename = "updated_at")]
-    updated_at: String,
+    pub created_at: String,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct Title {
     #[serde(rename = "id")]
-    id: i64,
+    pub id: i64,
 
     #[serde(rename = "name")]
     pub name: String,
@@ -469,26 +428,27 @@ pub struct Title {
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct TitlesUser {
     #[serde(rename = "id")]
-    id: i64,
+    pub id: i64,
 
     #[serde(rename = "user_id")]
-    user_id: i64,
+    pub user_id: i64,
 
     #[serde(rename = "title_id")]
-    title_id: i64,
+    pub title_id: i64,
 
     #[serde(rename = "selected")]
-    selected: bool,
+    pub selected: bool,
 
     #[serde(rename = "created_at")]
-    created_at: String,
+    pub created_at: String,
 
     #[serde(rename = "updated_at")]
-    updated_at: String,
+    pub updated_at: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub enum Kind {
+    #[default]
     #[serde(rename = "pedagogy")]
     Pedagogy,
 
@@ -500,15 +460,12 @@ pub enum Kind {
 
     #[serde(rename = "social")]
     Social,
-}
 
-impl Default for Kind {
-    fn default() -> Self {
-        Kind::Pedagogy
-    }
+    #[serde(other)]
+    Unknown,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub enum Tier {
     #[serde(rename = "easy")]
     Easy,
@@ -519,30 +476,10 @@ pub enum Tier {
     #[serde(rename = "medium")]
     Medium,
 
+    #[default]
     #[serde(rename = "none")]
     None,
-}
-
-impl Default for Tier {
-    fn default() -> Self {
-        Tier::None
-    }
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub enum Status {
-    #[serde(rename = "finished")]
-    Finished,
-
-    #[serde(rename = "in_progress")]
-    InProgress,
-
-    #[serde(rename = "waiting_for_correction")]
-    WaitingForCorrection,
-}
 
-impl Default for Status {
-    fn default() -> Self {
-        Status::WaitingForCorrection
-    }
+    #[serde(other)]
+    Unknown,
 }
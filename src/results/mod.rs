@@ -0,0 +1,18 @@
+pub mod achievements;
+pub mod campus;
+pub mod coalitions;
+pub mod cursus;
+pub mod events;
+pub mod exams;
+pub mod locations;
+pub mod me;
+pub mod projects;
+pub mod scale_teams;
+pub mod slots;
+pub mod teams;
+pub mod users;
+
+/// Whether `nbr_subscribers` is still below `max_people`, no maximum means no limit.
+pub(crate) fn has_free_seats(max_people: Option<i64>, nbr_subscribers: i64) -> bool {
+    max_people.is_none_or(|max| nbr_subscribers < max)
+}
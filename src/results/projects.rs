@@ -0,0 +1,264 @@
+extern crate serde_json;
+use crate::paginate::Paginator;
+use crate::results::campus::CampusElement;
+use crate::results::me::Cursus;
+use crate::results::teams::Team;
+use crate::{Session, SessionError};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Project {
+    #[serde(rename = "id")]
+    pub id: i64,
+
+    #[serde(rename = "name")]
+    pub name: String,
+
+    #[serde(rename = "slug")]
+    pub slug: String,
+
+    #[serde(rename = "difficulty")]
+    pub difficulty: Option<i64>,
+
+    #[serde(rename = "parent")]
+    pub parent: Option<ProjectRef>,
+
+    #[serde(rename = "children")]
+    pub children: Vec<ProjectRef>,
+
+    #[serde(rename = "attachments")]
+    pub attachments: Vec<serde_json::Value>,
+
+    #[serde(rename = "created_at")]
+    pub created_at: String,
+
+    #[serde(rename = "updated_at")]
+    pub updated_at: String,
+
+    #[serde(rename = "exam")]
+    pub exam: bool,
+
+    #[serde(rename = "git_id")]
+    pub git_id: Option<i64>,
+
+    #[serde(rename = "repository")]
+    pub repository: Option<String>,
+
+    #[serde(rename = "cursus")]
+    pub cursus: Vec<Cursus>,
+
+    #[serde(rename = "campus")]
+    pub campus: Vec<CampusElement>,
+
+    #[serde(rename = "videos")]
+    pub videos: Vec<serde_json::Value>,
+
+    #[serde(rename = "project_sessions")]
+    pub project_sessions: Vec<ProjectSession>,
+}
+
+/// Short form of a project, as nested in other resources.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ProjectRef {
+    #[serde(rename = "id")]
+    pub id: i64,
+
+    #[serde(rename = "name")]
+    pub name: String,
+
+    #[serde(rename = "slug")]
+    pub slug: String,
+
+    #[serde(rename = "parent_id")]
+    pub parent_id: Option<i64>,
+}
+
+/// A project as run on a given campus and cursus.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ProjectSession {
+    #[serde(rename = "id")]
+    pub id: i64,
+
+    #[serde(rename = "solo")]
+    pub solo: Option<bool>,
+
+    #[serde(rename = "begin_at")]
+    pub begin_at: Option<String>,
+
+    #[serde(rename = "end_at")]
+    pub end_at: Option<String>,
+
+    #[serde(rename = "estimate_time")]
+    pub estimate_time: Option<String>,
+
+    #[serde(rename = "difficulty")]
+    pub difficulty: Option<i64>,
+
+    #[serde(rename = "objectives")]
+    pub objectives: Vec<String>,
+
+    #[serde(rename = "description")]
+    pub description: Option<String>,
+
+    #[serde(rename = "duration_days")]
+    pub duration_days: Option<i64>,
+
+    #[serde(rename = "terminating_after")]
+    pub terminating_after: Option<i64>,
+
+    #[serde(rename = "project_id")]
+    pub project_id: i64,
+
+    #[serde(rename = "campus_id")]
+    pub campus_id: Option<i64>,
+
+    #[serde(rename = "cursus_id")]
+    pub cursus_id: Option<i64>,
+
+    #[serde(rename = "created_at")]
+    pub created_at: String,
+
+    #[serde(rename = "updated_at")]
+    pub updated_at: String,
+
+    #[serde(rename = "max_people")]
+    pub max_people: Option<i64>,
+
+    #[serde(rename = "is_subscriptable")]
+    pub is_subscriptable: Option<bool>,
+
+    #[serde(rename = "team_behaviour")]
+    pub team_behaviour: Option<String>,
+
+    #[serde(rename = "commit")]
+    pub commit: Option<String>,
+}
+
+/// A user's registration to a project.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ProjectsUser {
+    #[serde(rename = "id")]
+    pub id: i64,
+
+    #[serde(rename = "occurrence")]
+    pub occurrence: i64,
+
+    #[serde(rename = "final_mark")]
+    pub final_mark: Option<i64>,
+
+    #[serde(rename = "status")]
+    pub status: Status,
+
+    #[serde(rename = "validated?")]
+    pub validated: Option<bool>,
+
+    #[serde(rename = "current_team_id")]
+    pub current_team_id: Option<i64>,
+
+    #[serde(rename = "project")]
+    pub project: ProjectRef,
+
+    #[serde(rename = "cursus_ids")]
+    pub cursus_ids: Vec<i64>,
+
+    #[serde(rename = "marked_at")]
+    pub marked_at: Option<String>,
+
+    #[serde(rename = "marked")]
+    pub marked: bool,
+
+    #[serde(rename = "retriable_at")]
+    pub retriable_at: Option<String>,
+
+    #[serde(rename = "created_at")]
+    pub created_at: String,
+
+    #[serde(rename = "updated_at")]
+    pub updated_at: String,
+
+    /// Only sent by the `projects_users` endpoints, not when nested in a user.
+    #[serde(rename = "teams", default)]
+    pub teams: Vec<Team>,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Status {
+    #[serde(rename = "finished")]
+    Finished,
+
+    #[serde(rename = "in_progress")]
+    InProgress,
+
+    #[default]
+    #[serde(rename = "waiting_for_correction")]
+    WaitingForCorrection,
+
+    #[serde(rename = "searching_a_group")]
+    SearchingAGroup,
+
+    #[serde(rename = "creating_group")]
+    CreatingGroup,
+
+    #[serde(rename = "waiting_to_start")]
+    WaitingToStart,
+
+    #[serde(rename = "parent")]
+    Parent,
+
+    #[serde(other)]
+    Unknown,
+}
+
+impl Session {
+    /// `/v2/projects`
+    pub fn projects(&mut self) -> Paginator<'_, Project> {
+        self.paginate("v2/projects")
+    }
+    /// `/v2/projects/:id`, by id or slug.
+    pub async fn project<T: fmt::Display>(&mut self, id: T) -> Result<Project, SessionError> {
+        self.get(&format!("v2/projects/{}", id)).await
+    }
+    /// `/v2/cursus/:id/projects`
+    pub fn cursus_projects(&mut self, cursus_id: i64) -> Paginator<'_, Project> {
+        self.paginate(&format!("v2/cursus/{}/projects", cursus_id))
+    }
+    /// `/v2/users/:id/projects_users`, by login or id.
+    pub fn user_projects_users<T: fmt::Display>(
+        &mut self,
+        login_or_id: T,
+    ) -> Paginator<'_, ProjectsUser> {
+        self.paginate(&format!("v2/users/{}/projects_users", login_or_id))
+    }
+}
+
+#[test]
+fn projects_user_deserialize_test() {
+    let json = r#"{
+        "id": 1, "occurrence": 0, "final_mark": 100, "status": "finished",
+        "validated?": true, "current_team_id": 3,
+        "project": {"id": 1, "name": "Libft", "slug": "42cursus-libft", "parent_id": null},
+        "cursus_ids": [21], "marked_at": "2021-07-01T10:00:00.000Z", "marked": true,
+        "retriable_at": null, "created_at": "2021-06-01T10:00:00.000Z",
+        "updated_at": "2021-07-01T10:00:00.000Z",
+        "teams": [{
+            "id": 3, "name": "jpark's group", "url": "https://api.intra.42.fr/v2/teams/3",
+            "final_mark": 100, "project_id": 1, "created_at": "2021-06-01T10:00:00.000Z",
+            "updated_at": "2021-07-01T10:00:00.000Z", "status": "finished",
+            "terminating_at": null,
+            "users": [{"id": 7, "login": "jpark", "url": "https://api.intra.42.fr/v2/users/jpark",
+                       "leader": true, "occurrence": 0, "validated": true, "projects_user_id": 1}],
+            "locked?": true, "validated?": true, "closed?": true,
+            "repo_url": "git@vogsphere.42seoul.kr:vogsphere/intra-uuid", "repo_uuid": "intra-uuid",
+            "locked_at": "2021-06-01T10:00:00.000Z", "closed_at": "2021-06-20T10:00:00.000Z",
+            "project_session_id": 2, "project_gitlab_path": null
+        }]
+    }"#;
+    let projects_user: ProjectsUser = serde_json::from_str(json).unwrap();
+    assert_eq!(projects_user.status, Status::Finished);
+    assert_eq!(projects_user.project.slug, "42cursus-libft");
+    assert_eq!(projects_user.teams[0].users[0].login, "jpark");
+
+    let status: Status = serde_json::from_str("\"something_new\"").unwrap();
+    assert_eq!(status, Status::Unknown);
+}
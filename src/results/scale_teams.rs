@@ -0,0 +1,312 @@
+extern crate serde_json;
+use crate::paginate::{with_params, Paginator};
+use crate::results::teams::Team;
+use crate::results::users::UserRef;
+use crate::{Session, SessionError};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// An evaluation: one corrector grading a team.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScaleTeam {
+    #[serde(rename = "id")]
+    pub id: i64,
+
+    #[serde(rename = "scale_id")]
+    pub scale_id: i64,
+
+    #[serde(rename = "comment")]
+    pub comment: Option<String>,
+
+    #[serde(rename = "created_at")]
+    pub created_at: String,
+
+    #[serde(rename = "updated_at")]
+    pub updated_at: String,
+
+    #[serde(rename = "feedback")]
+    pub feedback: Option<String>,
+
+    #[serde(rename = "final_mark")]
+    pub final_mark: Option<i64>,
+
+    #[serde(rename = "flag")]
+    pub flag: Option<Flag>,
+
+    #[serde(rename = "begin_at")]
+    pub begin_at: String,
+
+    #[serde(rename = "correcteds")]
+    pub correcteds: Participants,
+
+    #[serde(rename = "corrector")]
+    pub corrector: Participant,
+
+    #[serde(rename = "truant")]
+    pub truant: Option<serde_json::Value>,
+
+    #[serde(rename = "filled_at")]
+    pub filled_at: Option<String>,
+
+    #[serde(rename = "questions_with_answers")]
+    pub questions_with_answers: Vec<serde_json::Value>,
+
+    #[serde(rename = "scale")]
+    pub scale: Option<Scale>,
+
+    #[serde(rename = "team")]
+    pub team: Option<Team>,
+
+    #[serde(rename = "feedbacks")]
+    pub feedbacks: Vec<Feedback>,
+}
+
+/// The corrector of an evaluation, `"invisible"` until it is revealed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Participant {
+    User(UserRef),
+    Hidden(String),
+}
+
+/// The corrected users of an evaluation, `"invisible"` until revealed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Participants {
+    Users(Vec<UserRef>),
+    Hidden(String),
+}
+
+/// Outcome of an evaluation, e.g. `Ok`, `Outstanding project` or `Cheat`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Flag {
+    #[serde(rename = "id")]
+    pub id: i64,
+
+    #[serde(rename = "name")]
+    pub name: String,
+
+    #[serde(rename = "positive")]
+    pub positive: bool,
+
+    #[serde(rename = "icon")]
+    pub icon: String,
+
+    #[serde(rename = "created_at")]
+    pub created_at: Option<String>,
+
+    #[serde(rename = "updated_at")]
+    pub updated_at: Option<String>,
+}
+
+/// Grading sheet used for an evaluation.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Scale {
+    #[serde(rename = "id")]
+    pub id: i64,
+
+    #[serde(rename = "evaluation_id")]
+    pub evaluation_id: i64,
+
+    #[serde(rename = "name")]
+    pub name: String,
+
+    #[serde(rename = "is_primary")]
+    pub is_primary: bool,
+
+    #[serde(rename = "comment")]
+    pub comment: Option<String>,
+
+    #[serde(rename = "introduction_md")]
+    pub introduction_md: Option<String>,
+
+    #[serde(rename = "disclaimer_md")]
+    pub disclaimer_md: Option<String>,
+
+    #[serde(rename = "guidelines_md")]
+    pub guidelines_md: Option<String>,
+
+    #[serde(rename = "created_at")]
+    pub created_at: String,
+
+    #[serde(rename = "correction_number")]
+    pub correction_number: i64,
+
+    /// Length of an evaluation, in seconds.
+    #[serde(rename = "duration")]
+    pub duration: i64,
+
+    #[serde(rename = "manual_subscription")]
+    pub manual_subscription: bool,
+
+    #[serde(rename = "languages")]
+    pub languages: Vec<serde_json::Value>,
+
+    #[serde(rename = "flags")]
+    pub flags: Vec<Flag>,
+
+    #[serde(rename = "free")]
+    pub free: bool,
+}
+
+/// Feedback left by a corrected user about the evaluation.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Feedback {
+    #[serde(rename = "id")]
+    pub id: i64,
+
+    #[serde(rename = "user")]
+    pub user: Option<UserRef>,
+
+    #[serde(rename = "feedbackable_type")]
+    pub feedbackable_type: String,
+
+    #[serde(rename = "feedbackable_id")]
+    pub feedbackable_id: i64,
+
+    #[serde(rename = "comment")]
+    pub comment: Option<String>,
+
+    #[serde(rename = "rating")]
+    pub rating: Option<i64>,
+
+    #[serde(rename = "created_at")]
+    pub created_at: String,
+
+    #[serde(rename = "feedback_details", default)]
+    pub feedback_details: Vec<FeedbackDetail>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct FeedbackDetail {
+    #[serde(rename = "id")]
+    pub id: i64,
+
+    #[serde(rename = "rate")]
+    pub rate: i64,
+
+    #[serde(rename = "kind")]
+    pub kind: String,
+}
+
+/// Filters for the scale_teams endpoints. Dates are ISO 8601 strings.
+#[derive(Clone, Debug, Default)]
+pub struct ScaleTeamFilter {
+    begin_at: Option<(String, String)>,
+    user_id: Option<i64>,
+    team_id: Option<i64>,
+    future: Option<bool>,
+}
+
+impl ScaleTeamFilter {
+    pub fn new() -> Self {
+        ScaleTeamFilter::default()
+    }
+    /// Evaluations beginning between `from` and `to`.
+    pub fn begin_at<S: Into<String>>(mut self, from: S, to: S) -> Self {
+        self.begin_at = Some((from.into(), to.into()));
+        self
+    }
+    /// Evaluations involving the user, as corrector or corrected.
+    pub fn user_id(mut self, user_id: i64) -> Self {
+        self.user_id = Some(user_id);
+        self
+    }
+    pub fn team_id(mut self, team_id: i64) -> Self {
+        self.team_id = Some(team_id);
+        self
+    }
+    /// Only upcoming evaluations when `true`, only past ones when `false`.
+    pub fn future(mut self, future: bool) -> Self {
+        self.future = Some(future);
+        self
+    }
+    fn params(&self) -> Vec<(String, String)> {
+        let mut params = Vec::new();
+        if let Some((from, to)) = &self.begin_at {
+            params.push(("range[begin_at]".to_string(), format!("{},{}", from, to)));
+        }
+        if let Some(user_id) = self.user_id {
+            params.push(("filter[user_id]".to_string(), user_id.to_string()));
+        }
+        if let Some(team_id) = self.team_id {
+            params.push(("filter[team_id]".to_string(), team_id.to_string()));
+        }
+        if let Some(future) = self.future {
+            params.push(("filter[future]".to_string(), future.to_string()));
+        }
+        params
+    }
+}
+
+impl Session {
+    /// `/v2/scale_teams`
+    pub fn scale_teams(&mut self, filter: &ScaleTeamFilter) -> Paginator<'_, ScaleTeam> {
+        self.paginate(&with_params("v2/scale_teams", &filter.params()))
+    }
+    /// `/v2/scale_teams/:id`
+    pub async fn scale_team(&mut self, id: i64) -> Result<ScaleTeam, SessionError> {
+        self.get(&format!("v2/scale_teams/{}", id)).await
+    }
+    /// `/v2/users/:id/scale_teams/as_corrector`: evaluations the user gave.
+    pub fn user_scale_teams_as_corrector<T: fmt::Display>(
+        &mut self,
+        login_or_id: T,
+        filter: &ScaleTeamFilter,
+    ) -> Paginator<'_, ScaleTeam> {
+        let path = format!("v2/users/{}/scale_teams/as_corrector", login_or_id);
+        self.paginate(&with_params(&path, &filter.params()))
+    }
+    /// `/v2/users/:id/scale_teams/as_corrected`: evaluations the user received.
+    pub fn user_scale_teams_as_corrected<T: fmt::Display>(
+        &mut self,
+        login_or_id: T,
+        filter: &ScaleTeamFilter,
+    ) -> Paginator<'_, ScaleTeam> {
+        let path = format!("v2/users/{}/scale_teams/as_corrected", login_or_id);
+        self.paginate(&with_params(&path, &filter.params()))
+    }
+}
+
+#[test]
+fn scale_team_deserialize_test() {
+    let json = r#"{
+        "id": 10, "scale_id": 4, "comment": "Good job", "created_at": "2021-07-01T10:00:00.000Z",
+        "updated_at": "2021-07-01T11:00:00.000Z", "feedback": "Thanks", "final_mark": 125,
+        "flag": {"id": 9, "name": "Outstanding project", "positive": true, "icon": "star-1",
+                 "created_at": "2015-09-14T23:06:52.000Z", "updated_at": "2015-09-14T23:06:52.000Z"},
+        "begin_at": "2021-07-01T10:00:00.000Z",
+        "correcteds": [{"id": 7, "login": "jpark", "url": "https://api.intra.42.fr/v2/users/jpark"}],
+        "corrector": "invisible",
+        "truant": {}, "filled_at": null, "questions_with_answers": [], "scale": null,
+        "team": null,
+        "feedbacks": [{"id": 1, "user": null, "feedbackable_type": "ScaleTeam",
+                       "feedbackable_id": 10, "comment": "Nice", "rating": 4,
+                       "created_at": "2021-07-01T12:00:00.000Z"}]
+    }"#;
+    let scale_team: ScaleTeam = serde_json::from_str(json).unwrap();
+    assert_eq!(
+        scale_team.corrector,
+        Participant::Hidden("invisible".to_string())
+    );
+    assert!(
+        matches!(&scale_team.correcteds, Participants::Users(users) if users[0].login == "jpark")
+    );
+    assert!(scale_team.flag.unwrap().positive);
+    assert_eq!(scale_team.feedbacks[0].rating, Some(4));
+}
+
+#[test]
+fn scale_team_filter_test() {
+    let filter = ScaleTeamFilter::new()
+        .begin_at("2021-07-01", "2021-07-31")
+        .user_id(7);
+    assert_eq!(
+        with_params("v2/scale_teams", &filter.params()),
+        "v2/scale_teams?range%5Bbegin_at%5D=2021-07-01%2C2021-07-31&filter%5Buser_id%5D=7"
+    );
+    assert_eq!(
+        with_params("v2/scale_teams", &ScaleTeamFilter::new().params()),
+        "v2/scale_teams"
+    );
+}
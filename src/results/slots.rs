@@ -0,0 +1,144 @@
+use crate::paginate::Paginator;
+use crate::results::scale_teams::Participant;
+use crate::{Session, SessionError};
+use chrono::{DateTime, Duration, SecondsFormat, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Slots start and end on a multiple of this many minutes.
+pub const SLOT_ALIGNMENT_MINUTES: u32 = 15;
+
+/// Shortest slot the API accepts.
+pub const SLOT_MIN_DURATION: Duration = Duration::hours(1);
+
+/// A period in which a user is available to evaluate.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Slot {
+    #[serde(rename = "id")]
+    pub id: i64,
+
+    #[serde(rename = "begin_at")]
+    pub begin_at: DateTime<Utc>,
+
+    #[serde(rename = "end_at")]
+    pub end_at: DateTime<Utc>,
+
+    /// The evaluation booked on the slot, `"invisible"` for others' slots.
+    #[serde(rename = "scale_team")]
+    pub scale_team: Option<serde_json::Value>,
+
+    #[serde(rename = "user")]
+    pub user: Option<Participant>,
+}
+
+impl Slot {
+    pub fn is_booked(&self) -> bool {
+        self.scale_team.is_some()
+    }
+}
+
+/// Checks a new slot against the rules of the API before sending it.
+pub fn validate_slot(
+    begin_at: DateTime<Utc>,
+    end_at: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Result<(), SessionError> {
+    let aligned = |at: DateTime<Utc>| {
+        at.minute().is_multiple_of(SLOT_ALIGNMENT_MINUTES)
+            && at.second() == 0
+            && at.nanosecond() == 0
+    };
+    if !aligned(begin_at) || !aligned(end_at) {
+        return Err(SessionError::InvalidSlot(format!(
+            "begin_at and end_at must be aligned on {} minutes",
+            SLOT_ALIGNMENT_MINUTES
+        )));
+    }
+    if begin_at <= now {
+        return Err(SessionError::InvalidSlot(
+            "begin_at must be in the future".to_string(),
+        ));
+    }
+    if end_at - begin_at < SLOT_MIN_DURATION {
+        return Err(SessionError::InvalidSlot(format!(
+            "a slot must last at least {} minutes",
+            SLOT_MIN_DURATION.num_minutes()
+        )));
+    }
+    Ok(())
+}
+
+impl Session {
+    /// `/v2/slots`
+    pub fn slots(&mut self) -> Paginator<'_, Slot> {
+        self.paginate("v2/slots")
+    }
+    /// `/v2/users/:id/slots`
+    pub fn user_slots<T: fmt::Display>(&mut self, login_or_id: T) -> Paginator<'_, Slot> {
+        self.paginate(&format!("v2/users/{}/slots", login_or_id))
+    }
+    /// `/v2/projects/:id/slots`: slots open for evaluations of a project.
+    pub fn project_slots(&mut self, project_id: i64) -> Paginator<'_, Slot> {
+        self.paginate(&format!("v2/projects/{}/slots", project_id))
+    }
+    /// Opens a slot with `POST /v2/slots` once `validate_slot` passes.
+    ///
+    /// The API splits it into several slots, all of them are returned.
+    pub async fn create_slot(
+        &mut self,
+        user_id: i64,
+        begin_at: DateTime<Utc>,
+        end_at: DateTime<Utc>,
+    ) -> Result<Vec<Slot>, SessionError> {
+        validate_slot(begin_at, end_at, Utc::now())?;
+        let form = [
+            ("slot[user_id]".to_string(), user_id.to_string()),
+            (
+                "slot[begin_at]".to_string(),
+                begin_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+            ),
+            (
+                "slot[end_at]".to_string(),
+                end_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+            ),
+        ];
+        self.post("v2/slots", &form).await
+    }
+    /// Removes a slot with `DELETE /v2/slots/:id`.
+    pub async fn delete_slot(&mut self, slot_id: i64) -> Result<(), SessionError> {
+        self.delete(&format!("v2/slots/{}", slot_id)).await
+    }
+}
+
+#[test]
+fn validate_slot_test() {
+    use chrono::TimeZone;
+
+    let at = |h, m| Utc.with_ymd_and_hms(2022, 3, 2, h, m, 0).unwrap();
+    let now = at(8, 0);
+    assert!(validate_slot(at(10, 0), at(11, 15), now).is_ok());
+    assert!(matches!(
+        validate_slot(at(10, 5), at(11, 15), now),
+        Err(SessionError::InvalidSlot(_))
+    ));
+    assert!(validate_slot(at(10, 0), at(10, 45), now).is_err());
+    assert!(validate_slot(at(7, 0), at(9, 0), now).is_err());
+}
+
+#[test]
+fn slot_deserialize_test() {
+    let json = r#"[
+        {"id": 1, "begin_at": "2022-03-02T10:00:00.000Z", "end_at": "2022-03-02T10:15:00.000Z",
+         "scale_team": null,
+         "user": {"id": 7, "login": "jpark", "url": "https://api.intra.42.fr/v2/users/jpark"}},
+        {"id": 2, "begin_at": "2022-03-02T10:15:00.000Z", "end_at": "2022-03-02T10:30:00.000Z",
+         "scale_team": "invisible", "user": "invisible"}
+    ]"#;
+    let slots: Vec<Slot> = serde_json::from_str(json).unwrap();
+    assert!(!slots[0].is_booked());
+    assert!(slots[1].is_booked());
+    assert_eq!(
+        slots[1].user,
+        Some(Participant::Hidden("invisible".to_string()))
+    );
+}
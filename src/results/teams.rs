@@ -0,0 +1,159 @@
+extern crate serde_json;
+use crate::paginate::Paginator;
+use crate::results::projects::{ProjectsUser, Status};
+use crate::results::scale_teams::ScaleTeam;
+use crate::{Session, SessionError};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Team {
+    #[serde(rename = "id")]
+    pub id: i64,
+
+    #[serde(rename = "name")]
+    pub name: String,
+
+    #[serde(rename = "url")]
+    pub url: String,
+
+    #[serde(rename = "final_mark")]
+    pub final_mark: Option<i64>,
+
+    #[serde(rename = "project_id")]
+    pub project_id: i64,
+
+    #[serde(rename = "created_at")]
+    pub created_at: String,
+
+    #[serde(rename = "updated_at")]
+    pub updated_at: String,
+
+    #[serde(rename = "status")]
+    pub status: Status,
+
+    #[serde(rename = "terminating_at")]
+    pub terminating_at: Option<String>,
+
+    #[serde(rename = "users")]
+    pub users: Vec<TeamUser>,
+
+    #[serde(rename = "locked?")]
+    pub locked: bool,
+
+    #[serde(rename = "validated?")]
+    pub validated: Option<bool>,
+
+    #[serde(rename = "closed?")]
+    pub closed: bool,
+
+    #[serde(rename = "repo_url")]
+    pub repo_url: Option<String>,
+
+    #[serde(rename = "repo_uuid")]
+    pub repo_uuid: Option<String>,
+
+    #[serde(rename = "locked_at")]
+    pub locked_at: Option<String>,
+
+    #[serde(rename = "closed_at")]
+    pub closed_at: Option<String>,
+
+    #[serde(rename = "project_session_id")]
+    pub project_session_id: Option<i64>,
+
+    #[serde(rename = "project_gitlab_path")]
+    pub project_gitlab_path: Option<String>,
+
+    /// Evaluations of the team, only sent by the teams endpoints.
+    #[serde(rename = "scale_teams", default)]
+    pub scale_teams: Vec<ScaleTeam>,
+}
+
+impl Team {
+    pub fn leader(&self) -> Option<&TeamUser> {
+        self.users.iter().find(|user| user.leader)
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct TeamUser {
+    #[serde(rename = "id")]
+    pub id: i64,
+
+    #[serde(rename = "login")]
+    pub login: String,
+
+    #[serde(rename = "url")]
+    pub url: String,
+
+    #[serde(rename = "leader")]
+    pub leader: bool,
+
+    #[serde(rename = "occurrence")]
+    pub occurrence: i64,
+
+    #[serde(rename = "validated")]
+    pub validated: bool,
+
+    #[serde(rename = "projects_user_id")]
+    pub projects_user_id: i64,
+}
+
+impl Session {
+    /// `/v2/teams/:id`
+    pub async fn team(&mut self, team_id: i64) -> Result<Team, SessionError> {
+        self.get(&format!("v2/teams/{}", team_id)).await
+    }
+    /// `/v2/users/:id/teams`
+    pub fn user_teams<T: fmt::Display>(&mut self, login_or_id: T) -> Paginator<'_, Team> {
+        self.paginate(&format!("v2/users/{}/teams", login_or_id))
+    }
+    /// `/v2/projects/:id/teams`
+    pub fn project_teams(&mut self, project_id: i64) -> Paginator<'_, Team> {
+        self.paginate(&format!("v2/projects/{}/teams", project_id))
+    }
+    /// Resolves `ProjectsUser::current_team_id`.
+    pub async fn current_team(
+        &mut self,
+        projects_user: &ProjectsUser,
+    ) -> Result<Option<Team>, SessionError> {
+        match projects_user.current_team_id {
+            Some(team_id) => Ok(Some(self.team(team_id).await?)),
+            None => Ok(None),
+        }
+    }
+}
+
+#[test]
+fn team_deserialize_test() {
+    let json = r#"{
+        "id": 3, "name": "jpark's group", "url": "https://api.intra.42.fr/v2/teams/3",
+        "final_mark": 84, "project_id": 1314, "created_at": "2022-01-01T10:00:00.000Z",
+        "updated_at": "2022-01-20T10:00:00.000Z", "status": "finished", "terminating_at": null,
+        "users": [
+            {"id": 7, "login": "jpark", "url": "https://api.intra.42.fr/v2/users/jpark",
+             "leader": false, "occurrence": 0, "validated": true, "projects_user_id": 1},
+            {"id": 8, "login": "bagmeg", "url": "https://api.intra.42.fr/v2/users/bagmeg",
+             "leader": true, "occurrence": 0, "validated": true, "projects_user_id": 2}
+        ],
+        "locked?": true, "validated?": true, "closed?": true,
+        "repo_url": "git@vogsphere.42seoul.kr:vogsphere/intra-uuid", "repo_uuid": "intra-uuid",
+        "locked_at": "2022-01-02T10:00:00.000Z", "closed_at": "2022-01-15T10:00:00.000Z",
+        "project_session_id": 2, "project_gitlab_path": null,
+        "scale_teams": [{
+            "id": 10, "scale_id": 4, "comment": "Clean code", "created_at": "2022-01-16T10:00:00.000Z",
+            "updated_at": "2022-01-16T11:00:00.000Z", "feedback": null, "final_mark": 84,
+            "flag": null, "begin_at": "2022-01-16T10:00:00.000Z",
+            "correcteds": [{"id": 7, "login": "jpark", "url": "https://api.intra.42.fr/v2/users/jpark"}],
+            "corrector": {"id": 9, "login": "eval", "url": "https://api.intra.42.fr/v2/users/eval"},
+            "truant": {}, "filled_at": "2022-01-16T10:40:00.000Z", "questions_with_answers": [],
+            "scale": null, "team": null, "feedbacks": []
+        }]
+    }"#;
+    let team: Team = serde_json::from_str(json).unwrap();
+    assert_eq!(team.leader().unwrap().login, "bagmeg");
+    assert_eq!(team.status, Status::Finished);
+    assert_eq!(team.scale_teams.len(), 1);
+    assert_eq!(team.scale_teams[0].final_mark, Some(84));
+}
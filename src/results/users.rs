@@ -0,0 +1,211 @@
+extern crate serde_json;
+use crate::results::me::{
+    Achievement, Campus, CampusUser, CursusUser, ExpertisesUser, LanguagesUser, ProjectsUser,
+    Title, TitlesUser,
+};
+use crate::{Session, SessionError};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Full profile returned by `/v2/users/:id`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UserDetail {
+    #[serde(rename = "id")]
+    pub id: i64,
+
+    #[serde(rename = "email")]
+    pub email: String,
+
+    #[serde(rename = "login")]
+    pub login: String,
+
+    #[serde(rename = "first_name")]
+    pub first_name: String,
+
+    #[serde(rename = "last_name")]
+    pub last_name: String,
+
+    #[serde(rename = "usual_full_name")]
+    pub usual_full_name: String,
+
+    #[serde(rename = "usual_first_name")]
+    pub usual_first_name: Option<String>,
+
+    #[serde(rename = "url")]
+    pub url: String,
+
+    #[serde(rename = "phone")]
+    pub phone: Option<String>,
+
+    #[serde(rename = "displayname")]
+    pub displayname: String,
+
+    #[serde(rename = "kind")]
+    pub kind: Option<String>,
+
+    #[serde(rename = "image")]
+    pub image: Option<Image>,
+
+    #[serde(rename = "staff?")]
+    pub staff: bool,
+
+    #[serde(rename = "correction_point")]
+    pub correction_point: i64,
+
+    #[serde(rename = "pool_month")]
+    pub pool_month: Option<String>,
+
+    #[serde(rename = "pool_year")]
+    pub pool_year: Option<String>,
+
+    #[serde(rename = "location")]
+    pub location: Option<String>,
+
+    #[serde(rename = "wallet")]
+    pub wallet: i64,
+
+    #[serde(rename = "anonymize_date")]
+    pub anonymize_date: Option<String>,
+
+    #[serde(rename = "data_erasure_date")]
+    pub data_erasure_date: Option<String>,
+
+    #[serde(rename = "created_at")]
+    pub created_at: String,
+
+    #[serde(rename = "updated_at")]
+    pub updated_at: String,
+
+    #[serde(rename = "alumnized_at")]
+    pub alumnized_at: Option<String>,
+
+    #[serde(rename = "alumni?")]
+    pub alumni: bool,
+
+    #[serde(rename = "active?")]
+    pub active: bool,
+
+    #[serde(rename = "groups")]
+    pub groups: Vec<Group>,
+
+    #[serde(rename = "cursus_users")]
+    pub cursus_users: Vec<CursusUser>,
+
+    #[serde(rename = "projects_users")]
+    pub projects_users: Vec<ProjectsUser>,
+
+    #[serde(rename = "languages_users")]
+    pub languages_users: Vec<LanguagesUser>,
+
+    #[serde(rename = "achievements")]
+    pub achievements: Vec<Achievement>,
+
+    #[serde(rename = "titles")]
+    pub titles: Vec<Title>,
+
+    #[serde(rename = "titles_users")]
+    pub titles_users: Vec<TitlesUser>,
+
+    #[serde(rename = "partnerships")]
+    pub partnerships: Vec<serde_json::Value>,
+
+    #[serde(rename = "patroned")]
+    pub patroned: Vec<serde_json::Value>,
+
+    #[serde(rename = "patroning")]
+    pub patroning: Vec<serde_json::Value>,
+
+    #[serde(rename = "expertises_users")]
+    pub expertises_users: Vec<ExpertisesUser>,
+
+    #[serde(rename = "roles")]
+    pub roles: Vec<serde_json::Value>,
+
+    #[serde(rename = "campus")]
+    pub campus: Vec<Campus>,
+
+    #[serde(rename = "campus_users")]
+    pub campus_users: Vec<CampusUser>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Image {
+    #[serde(rename = "link")]
+    pub link: Option<String>,
+
+    #[serde(rename = "versions")]
+    pub versions: Option<ImageVersions>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ImageVersions {
+    #[serde(rename = "large")]
+    pub large: Option<String>,
+
+    #[serde(rename = "medium")]
+    pub medium: Option<String>,
+
+    #[serde(rename = "small")]
+    pub small: Option<String>,
+
+    #[serde(rename = "micro")]
+    pub micro: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Group {
+    #[serde(rename = "id")]
+    pub id: i64,
+
+    #[serde(rename = "name")]
+    pub name: String,
+}
+
+/// Short form of a user, as nested in other resources.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserRef {
+    #[serde(rename = "id")]
+    pub id: i64,
+
+    #[serde(rename = "login")]
+    pub login: String,
+
+    #[serde(rename = "url")]
+    pub url: String,
+}
+
+impl Session {
+    /// `/v2/users/:id`: profile of a user, looked up by login or id.
+    pub async fn user<T: fmt::Display>(
+        &mut self,
+        login_or_id: T,
+    ) -> Result<UserDetail, SessionError> {
+        self.get(&format!("v2/users/{}", login_or_id)).await
+    }
+}
+
+#[test]
+fn user_detail_deserialize_test() {
+    let json = r#"{
+        "id": 2, "email": "andre@42.fr", "login": "andre",
+        "first_name": "André", "last_name": "Aubin",
+        "usual_full_name": "Juliette Aubin", "usual_first_name": "Juliette",
+        "url": "https://api.intra.42.fr/v2/users/andre", "phone": null,
+        "displayname": "André Aubin", "kind": "admin",
+        "image": {"link": "https://cdn.intra.42.fr/users/andre.jpg", "versions": {"large": null, "medium": null, "small": null, "micro": null}},
+        "staff?": true, "correction_point": 4, "pool_month": "july", "pool_year": "2016",
+        "location": null, "wallet": 0, "anonymize_date": "2021-02-20T00:00:00.000+03:00",
+        "data_erasure_date": null, "created_at": "2017-11-22T13:41:00.825Z",
+        "updated_at": "2017-11-22T13:41:26.243Z", "alumnized_at": null,
+        "alumni?": false, "active?": true,
+        "groups": [], "cursus_users": [], "projects_users": [], "languages_users": [],
+        "achievements": [], "titles": [], "titles_users": [], "partnerships": [],
+        "patroned": [], "patroning": [], "expertises_users": [], "roles": [],
+        "campus": [], "campus_users": []
+    }"#;
+    let user: UserDetail = serde_json::from_str(json).unwrap();
+    assert_eq!(user.login, "andre");
+    assert_eq!(user.phone, None);
+    assert!(user.staff);
+    assert!(user.image.unwrap().link.is_some());
+}
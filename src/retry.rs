@@ -0,0 +1,134 @@
+use crate::SessionError;
+use rand::Rng;
+use serde::{Deserialize, Deserializer};
+use std::time::Duration;
+
+/// When and how often a failed request is sent again.
+///
+/// The delay doubles after every attempt, starting at `base_delay` and capped
+/// at `max_delay`. A 429 carrying `Retry-After` waits for the requested delay.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// Total attempts, the first one included. `1` disables retries.
+    pub max_attempts: u32,
+    #[serde(rename = "base_delay_ms", deserialize_with = "millis")]
+    pub base_delay: Duration,
+    #[serde(rename = "max_delay_ms", deserialize_with = "millis")]
+    pub max_delay: Duration,
+    /// Picks a random delay between half and all of the computed one.
+    pub jitter: bool,
+    /// Response statuses worth retrying.
+    pub retry_statuses: Vec<u16>,
+    /// Retry connection failures, resets and timeouts.
+    pub retry_connection_errors: bool,
+}
+
+fn millis<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+    Ok(Duration::from_millis(u64::deserialize(deserializer)?))
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: true,
+            retry_statuses: vec![429, 500, 502, 503, 504],
+            retry_connection_errors: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Policy that never retries.
+    pub fn none() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            ..RetryPolicy::default()
+        }
+    }
+
+    pub fn is_retryable(&self, err: &SessionError) -> bool {
+        match err {
+            SessionError::ReqwestError(e) => {
+                self.retry_connection_errors
+                    && (e.is_connect() || e.is_timeout() || e.is_request() || e.is_body())
+            }
+            _ => match err.status() {
+                Some(status) => self.retry_statuses.contains(&status.as_u16()),
+                None => false,
+            },
+        }
+    }
+
+    /// Delay before the next attempt after `attempt` failed with `err`,
+    /// `None` when the error should be returned instead.
+    pub fn delay(&self, attempt: u32, err: &SessionError) -> Option<Duration> {
+        if attempt >= self.max_attempts || !self.is_retryable(err) {
+            return None;
+        }
+        if let SessionError::RateLimited {
+            retry_after: Some(retry_after),
+            ..
+        } = err
+        {
+            return Some(*retry_after);
+        }
+        let exponent = attempt.saturating_sub(1).min(31);
+        let delay = self
+            .base_delay
+            .saturating_mul(1 << exponent)
+            .min(self.max_delay);
+        if self.jitter && !delay.is_zero() {
+            return Some(rand::thread_rng().gen_range(delay / 2..=delay));
+        }
+        Some(delay)
+    }
+}
+
+#[test]
+fn retry_policy_delay_test() {
+    use crate::ErrorResponse;
+    use reqwest::StatusCode;
+
+    let policy = RetryPolicy {
+        jitter: false,
+        ..RetryPolicy::default()
+    };
+    let error = |status: StatusCode| {
+        SessionError::ServerError(ErrorResponse::new(status, "v2/me", String::new()))
+    };
+    let unavailable = error(StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(
+        policy.delay(1, &unavailable),
+        Some(Duration::from_millis(500))
+    );
+    assert_eq!(policy.delay(2, &unavailable), Some(Duration::from_secs(1)));
+    assert_eq!(policy.delay(3, &unavailable), None);
+    assert_eq!(policy.delay(1, &error(StatusCode::NOT_IMPLEMENTED)), None);
+
+    let rate_limited = SessionError::RateLimited {
+        retry_after: Some(Duration::from_secs(7)),
+        response: ErrorResponse::new(StatusCode::TOO_MANY_REQUESTS, "v2/me", String::new()),
+    };
+    assert_eq!(policy.delay(1, &rate_limited), Some(Duration::from_secs(7)));
+    assert_eq!(RetryPolicy::none().delay(1, &rate_limited), None);
+}
+
+#[test]
+fn retry_policy_toml_test() {
+    let policy: RetryPolicy = toml::from_str(
+        r#"
+        max_attempts = 5
+        base_delay_ms = 100
+        retry_statuses = [503]
+        "#,
+    )
+    .unwrap();
+    assert_eq!(policy.max_attempts, 5);
+    assert_eq!(policy.base_delay, Duration::from_millis(100));
+    assert_eq!(policy.max_delay, Duration::from_secs(30));
+    assert_eq!(policy.retry_statuses, [503]);
+}
@@ -0,0 +1,154 @@
+use crate::SessionError;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub(crate) fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
+/// Everything the token endpoint handed out, kept across runs.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredToken {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    /// Unix timestamp, in seconds, at which the access token expires.
+    pub expires_at: Option<i64>,
+}
+
+/// Where a `Session` persists its tokens between runs.
+pub trait TokenStore: fmt::Debug + Send + Sync {
+    /// Returns the saved token, `None` when nothing was saved yet.
+    fn load(&self) -> Result<Option<StoredToken>, SessionError>;
+    fn save(&self, token: &StoredToken) -> Result<(), SessionError>;
+    fn clear(&self) -> Result<(), SessionError>;
+}
+
+/// Stores the token as JSON in a file readable only by its owner.
+#[derive(Clone, Debug)]
+pub struct FileTokenStore {
+    path: PathBuf,
+}
+
+impl FileTokenStore {
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        FileTokenStore {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+    fn json_error(&self, source: serde_json::Error) -> SessionError {
+        SessionError::JsonError {
+            path: self.path.display().to_string(),
+            source,
+        }
+    }
+}
+
+impl TokenStore for FileTokenStore {
+    fn load(&self) -> Result<Option<StoredToken>, SessionError> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let token = serde_json::from_str(&content).map_err(|e| self.json_error(e))?;
+        Ok(Some(token))
+    }
+    fn save(&self, token: &StoredToken) -> Result<(), SessionError> {
+        let content = serde_json::to_string_pretty(token).map_err(|e| self.json_error(e))?;
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        io::Write::write_all(&mut options.open(&self.path)?, content.as_bytes())?;
+        Ok(())
+    }
+    fn clear(&self) -> Result<(), SessionError> {
+        match fs::remove_file(&self.path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Keeps the token in memory only, mostly useful for tests.
+#[derive(Debug, Default)]
+pub struct MemoryTokenStore {
+    token: Mutex<Option<StoredToken>>,
+}
+
+impl MemoryTokenStore {
+    pub fn new() -> Self {
+        MemoryTokenStore::default()
+    }
+}
+
+impl TokenStore for MemoryTokenStore {
+    fn load(&self) -> Result<Option<StoredToken>, SessionError> {
+        Ok(self.token.lock().unwrap().clone())
+    }
+    fn save(&self, token: &StoredToken) -> Result<(), SessionError> {
+        *self.token.lock().unwrap() = Some(token.clone());
+        Ok(())
+    }
+    fn clear(&self) -> Result<(), SessionError> {
+        *self.token.lock().unwrap() = None;
+        Ok(())
+    }
+}
+
+/// Reads the `token_file` config entry as a `FileTokenStore`.
+pub(crate) fn deserialize_token_file<'de, D>(
+    deserializer: D,
+) -> Result<Option<Arc<dyn TokenStore>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let path: Option<PathBuf> = Option::deserialize(deserializer)?;
+    Ok(path.map(|path| Arc::new(FileTokenStore::new(path)) as Arc<dyn TokenStore>))
+}
+
+#[test]
+fn memory_token_store_test() {
+    let store = MemoryTokenStore::new();
+    assert_eq!(store.load().unwrap(), None);
+    let token = StoredToken {
+        access_token: "access".to_string(),
+        refresh_token: Some("refresh".to_string()),
+        scopes: vec!["public".to_string()],
+        expires_at: Some(1_700_000_000),
+    };
+    store.save(&token).unwrap();
+    assert_eq!(store.load().unwrap(), Some(token));
+    store.clear().unwrap();
+    assert_eq!(store.load().unwrap(), None);
+}
+
+#[test]
+fn file_token_store_test() {
+    let path = std::env::temp_dir().join(format!("ftapi-token-{}.json", std::process::id()));
+    let store = FileTokenStore::new(&path);
+    assert_eq!(store.load().unwrap(), None);
+    let token = StoredToken {
+        access_token: "access".to_string(),
+        refresh_token: None,
+        scopes: Vec::new(),
+        expires_at: None,
+    };
+    store.save(&token).unwrap();
+    assert_eq!(store.load().unwrap(), Some(token));
+    store.clear().unwrap();
+    assert!(!path.exists());
+}
@@ -1,17 +1,46 @@
+use crate::status_error;
+use crate::store::{unix_now, StoredToken};
+use crate::ErrorResponse;
+use crate::GrantType;
 use crate::Session;
 use crate::SessionError;
-use log::{self, debug};
-use oauth2::basic::BasicClient;
-use oauth2::reqwest::async_http_client;
+use log::{self, debug, warn};
+use oauth2::basic::{BasicClient, BasicTokenResponse};
 use oauth2::{
-    AuthUrl, AuthorizationCode, ClientId, ClientSecret, CsrfToken, RedirectUrl, Scope,
-    TokenResponse, TokenUrl,
+    AuthUrl, AuthorizationCode, ClientId, ClientSecret, CsrfToken, PkceCodeChallenge,
+    PkceCodeVerifier, RedirectUrl, RefreshToken, Scope, TokenResponse, TokenUrl,
 };
+use oauth2::{HttpRequest, HttpResponse};
+use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::net::TcpListener;
 use url::Url;
 
+/// Receives the authorization url in place of the default `Browse to:` line.
+#[derive(Clone)]
+pub struct AuthorizeCallback(Arc<dyn Fn(&Url) + Send + Sync>);
+
+impl AuthorizeCallback {
+    pub fn new<F: Fn(&Url) + Send + Sync + 'static>(callback: F) -> Self {
+        AuthorizeCallback(Arc::new(callback))
+    }
+    pub fn call(&self, url: &Url) {
+        (self.0)(url)
+    }
+}
+
+impl fmt::Debug for AuthorizeCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("AuthorizeCallback")
+    }
+}
+
+/// Scopes an application can request from the 42 API.
+pub const KNOWN_SCOPES: [&str; 6] = ["public", "projects", "profile", "elearning", "tig", "forum"];
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct TokenInfo {
     #[serde(rename = "resource_owner_id")]
@@ -36,18 +65,55 @@ struct Application {
     uid: Option<String>,
 }
 
-pub async fn token_info(token: Option<String>) -> Result<TokenInfo, SessionError> {
-    let url = format!(
-        "https://api.intra.42.fr/oauth/token/info?access_token={}",
-        token.unwrap_or_default()
-    );
-    let resp = reqwest::get(&url).await?;
+pub async fn token_info(
+    session: &Session,
+    token: Option<String>,
+) -> Result<TokenInfo, SessionError> {
+    let url = Url::parse_with_params(
+        &session.get_token_info_url(),
+        &[("access_token", token.unwrap_or_default())],
+    )?;
+    let policy = session.get_retry_policy();
+    let mut attempt = 1;
+    loop {
+        match token_info_once(session.get_http_client(), url.as_str()).await {
+            Err(err) => match policy.delay(attempt, &err) {
+                Some(delay) => {
+                    debug!(
+                        "token_info(): attempt {} failed, retrying in {:?}",
+                        attempt, delay
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                None => return Err(err),
+            },
+            token_info => return token_info,
+        }
+    }
+}
+
+async fn token_info_once(client: &reqwest::Client, url: &str) -> Result<TokenInfo, SessionError> {
+    let resp = client.get(url).send().await?;
+    let status = resp.status();
+    // An invalid token answers 401 with a body that parses as an empty TokenInfo.
+    if status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+        let body = resp.text().await.unwrap_or_default();
+        return Err(status_error(
+            status,
+            None,
+            ErrorResponse::new(status, url, body),
+        ));
+    }
     let token_info: TokenInfo = resp.json().await?;
     Ok(token_info)
 }
 
-pub async fn check_token_valide(token: Option<String>) -> Result<bool, SessionError> {
-    let token_info = token_info(token).await?;
+pub async fn check_token_valide(
+    session: &Session,
+    token: Option<String>,
+) -> Result<bool, SessionError> {
+    let token_info = token_info(session, token).await?;
     if token_info.expires_in_seconds.is_none() {
         return Ok(false);
     }
@@ -56,11 +122,12 @@ pub async fn check_token_valide(token: Option<String>) -> Result<bool, SessionEr
 
 #[tokio::test]
 async fn token_info_fail_test() {
-    let res = token_info(Some("not working token".to_string())).await;
-    // let res = token_info(None).await;
+    let session = Session::default();
+    let res = token_info(&session, Some("not working token".to_string())).await;
+    // let res = token_info(&session, None).await;
     if let Ok(token_info) = res {
         println!("{:?}", token_info); // cargo run test -- --nocapture
-        assert_eq!(token_info.application.is_none(), true);
+        assert!(token_info.application.is_none());
     }
 }
 
@@ -79,10 +146,11 @@ async fn token_info_success_test() {
 
 #[tokio::test]
 async fn check_token_valide_fail_test() {
-    let res = check_token_valide(Some("not working token".to_string())).await;
-    // let res = check_token_valide(None).await;
+    let session = Session::default();
+    let res = check_token_valide(&session, Some("not working token".to_string())).await;
+    // let res = check_token_valide(&session, None).await;
     if let Ok(t) = res {
-        assert_eq!(t, false);
+        assert!(!t);
     }
 }
 
@@ -99,31 +167,178 @@ async fn check_token_valide_success_test() {
 }
 */
 
-pub async fn generate_token(session: Session) -> Result<String, SessionError> {
-    let client = BasicClient::new(
+pub async fn generate_token(session: Session) -> Result<StoredToken, SessionError> {
+    if let Some(scope) = session
+        .get_scopes()
+        .iter()
+        .find(|scope| !KNOWN_SCOPES.contains(&scope.as_str()))
+    {
+        return Err(SessionError::UnknownScope(scope.clone()));
+    }
+    let client = oauth_client(&session)?;
+    match session.get_grant_type() {
+        GrantType::AuthorizationCode => authorization_code_token(&session, client).await,
+        GrantType::ClientCredentials => client_credentials_token(&session, &client).await,
+    }
+}
+
+fn oauth_client(session: &Session) -> Result<BasicClient, SessionError> {
+    Ok(BasicClient::new(
         ClientId::new(String::from(session.get_client_id())),
-        Some(ClientSecret::new(String::from(session.get_client_secret()))),
-        AuthUrl::new("https://api.intra.42.fr/oauth/authorize".to_string())?,
-        Some(TokenUrl::new(
-            "https://api.intra.42.fr/oauth/token".to_string(),
-        )?),
-    )
-    .set_redirect_uri(RedirectUrl::new("http://localhost:8080".to_string())?);
+        // Public clients authenticate with PKCE instead of a secret.
+        Some(session.get_client_secret())
+            .filter(|secret| !secret.is_empty())
+            .map(|secret| ClientSecret::new(secret.to_string())),
+        AuthUrl::new(session.get_auth_url())?,
+        Some(TokenUrl::new(session.get_token_url())?),
+    ))
+}
+
+/// Exchange a refresh token for a new access token.
+pub async fn refresh_token(
+    session: &Session,
+    refresh_token: &str,
+) -> Result<StoredToken, SessionError> {
+    let client = oauth_client(session)?;
+    let token = match client
+        .exchange_refresh_token(&RefreshToken::new(refresh_token.to_string()))
+        .request_async(|request| oauth_request(session.get_http_client(), request))
+        .await
+    {
+        Err(_) => return Err(SessionError::UnauthorizedServerError),
+        Ok(t) => t,
+    };
+    debug!("42API returned the following token:\n{:?}\n", token);
+    let mut stored = stored_token(&token, session.get_scopes());
+    if stored.refresh_token.is_none() {
+        stored.refresh_token = Some(refresh_token.to_string());
+    }
+    Ok(stored)
+}
+
+/// Keeps what the token endpoint returned so it can be reused later.
+fn stored_token(token: &BasicTokenResponse, requested_scopes: &[String]) -> StoredToken {
+    let scopes = match token.scopes() {
+        Some(scopes_vec) => scopes_vec
+            .iter()
+            .flat_map(|comma_separated| comma_separated.split(','))
+            .map(str::to_string)
+            .collect(),
+        None => requested_scopes.to_vec(),
+    };
+    let now = unix_now();
+    StoredToken {
+        access_token: token.access_token().secret().to_owned(),
+        refresh_token: token.refresh_token().map(|t| t.secret().to_owned()),
+        scopes,
+        expires_at: token.expires_in().map(|d| now + d.as_secs() as i64),
+    }
+}
+
+/// Sends an OAuth request with the session client instead of a fresh one.
+async fn oauth_request(
+    client: &reqwest::Client,
+    request: HttpRequest,
+) -> Result<HttpResponse, oauth2::reqwest::Error<reqwest::Error>> {
+    let mut builder = client
+        .request(request.method, request.url.as_str())
+        .body(request.body);
+    for (name, value) in &request.headers {
+        builder = builder.header(name, value);
+    }
+    let response = builder
+        .send()
+        .await
+        .map_err(oauth2::reqwest::Error::Reqwest)?;
+    let status_code = response.status();
+    let headers = response.headers().to_owned();
+    let body = response
+        .bytes()
+        .await
+        .map_err(oauth2::reqwest::Error::Reqwest)?;
+    Ok(HttpResponse {
+        status_code,
+        headers,
+        body: body.to_vec(),
+    })
+}
+
+fn scopes(session: &Session) -> impl Iterator<Item = Scope> + '_ {
+    session.get_scopes().iter().cloned().map(Scope::new)
+}
 
-    let (auth_url, _) = client
+async fn authorization_code_token(
+    session: &Session,
+    client: BasicClient,
+) -> Result<StoredToken, SessionError> {
+    let client = client.set_redirect_uri(RedirectUrl::new(session.get_redirect_url().to_string())?);
+
+    let mut request = client
         .authorize_url(CsrfToken::new_random)
-        .add_scope(Scope::new("public".to_string()))
-        .url();
-    println!("Browse to: {}", auth_url);
+        .add_scopes(scopes(session));
+    let pkce_verifier = if session.get_use_pkce() {
+        let (challenge, verifier) = PkceCodeChallenge::new_random_sha256();
+        request = request.set_pkce_challenge(challenge);
+        Some(verifier)
+    } else {
+        None
+    };
+    let (auth_url, csrf_state) = request.url();
+    if !session.is_interactive() {
+        return Err(SessionError::AuthenticationRequired {
+            url: auth_url.to_string(),
+        });
+    }
 
-    let ac_token = local_server(client).await?;
-    Ok(ac_token)
+    let listener = TcpListener::bind(session.get_bind_address()?).await?;
+    match session.get_authorize_callback() {
+        Some(callback) => callback.call(&auth_url),
+        None => println!("Browse to: {}", auth_url),
+    }
+    let login = local_server(
+        listener,
+        session.get_http_client(),
+        client,
+        csrf_state,
+        pkce_verifier,
+    );
+    let token = match session.get_login_timeout() {
+        Some(limit) => match tokio::time::timeout(limit, login).await {
+            Ok(token) => token?,
+            Err(_) => return Err(SessionError::LoginTimeout(limit)),
+        },
+        None => login.await?,
+    };
+    Ok(stored_token(&token, session.get_scopes()))
 }
 
-/// Create local server with port number 8000 and waits for user to finish authorize.
-async fn local_server(client: BasicClient) -> Result<String, SessionError> {
+/// Exchange the application credentials for a token, no browser involved.
+async fn client_credentials_token(
+    session: &Session,
+    client: &BasicClient,
+) -> Result<StoredToken, SessionError> {
+    let token = match client
+        .exchange_client_credentials()
+        .add_scopes(scopes(session))
+        .request_async(|request| oauth_request(session.get_http_client(), request))
+        .await
+    {
+        Err(_) => return Err(SessionError::UnauthorizedServerError),
+        Ok(t) => t,
+    };
+    debug!("42API returned the following token:\n{:?}\n", token);
+    Ok(stored_token(&token, session.get_scopes()))
+}
+
+/// Waits on `listener` for the authorization redirect and exchanges its code.
+async fn local_server(
+    listener: TcpListener,
+    http_client: &reqwest::Client,
+    client: BasicClient,
+    csrf_state: CsrfToken,
+    pkce_verifier: Option<PkceCodeVerifier>,
+) -> Result<BasicTokenResponse, SessionError> {
     let ac_token;
-    let listener = TcpListener::bind("127.0.0.1:8080").await.unwrap();
     loop {
         if let Ok((mut stream, _)) = listener.accept().await {
             let code;
@@ -132,35 +347,13 @@ async fn local_server(client: BasicClient) -> Result<String, SessionError> {
                 let mut reader = BufReader::new(&mut stream);
                 let mut request_line = String::new();
                 reader.read_line(&mut request_line).await?;
-                let redirect_url = match request_line.split_whitespace().nth(1) {
-                    Some(url) => url,
-                    None => return Err(SessionError::NoneError),
-                };
-                let url = Url::parse(&("http://localhost".to_string() + redirect_url))?;
-
-                let code_pair = match url.query_pairs().find(|pair| {
-                    let &(ref key, _) = pair;
-                    key == "code"
-                }) {
-                    Some(code) => code,
-                    None => return Err(SessionError::NoneError),
-                };
-
-                let (_, value) = code_pair;
-                code = AuthorizationCode::new(value.into_owned());
-
-                let state_pair = match url.query_pairs().find(|pair| {
-                    let &(ref key, _) = pair;
-                    key == "state"
-                }) {
-                    Some(state) => state,
-                    None => return Err(SessionError::NoneError),
-                };
-
-                let (_, value) = state_pair;
-                state = CsrfToken::new(value.into_owned());
+                (code, state) = parse_redirect(&request_line)?;
             }
-            let message = "Go back to your terminal :)";
+            let verified = verify_state(&csrf_state, &state);
+            let message = match verified {
+                Ok(()) => "Go back to your terminal :)",
+                Err(_) => "Authorization rejected: state does not match :(",
+            };
             let response = format!(
                 "HTTP/1.1 200 OK\r\ncontent-length: {}\r\n\r\n{}",
                 message.len(),
@@ -170,11 +363,15 @@ async fn local_server(client: BasicClient) -> Result<String, SessionError> {
 
             debug!("42API returned the following code:\n{}\n", code.secret());
             debug!("42API returned the following state:\n{}\n", state.secret());
+            verified?;
 
             // Exchange the code with a token.
-            let token_res = client
-                .exchange_code(code)
-                .request_async(async_http_client)
+            let mut token_req = client.exchange_code(code);
+            if let Some(verifier) = pkce_verifier {
+                token_req = token_req.set_pkce_verifier(verifier);
+            }
+            let token_res = token_req
+                .request_async(|request| oauth_request(http_client, request))
                 .await;
             let token = match token_res {
                 Err(_) => return Err(SessionError::UnauthorizedServerError),
@@ -185,21 +382,82 @@ async fn local_server(client: BasicClient) -> Result<String, SessionError> {
             let scopes = if let Some(scopes_vec) = token.scopes() {
                 scopes_vec
                     .iter()
-                    .map(|comma_separated| comma_separated.split(','))
-                    .flatten()
+                    .flat_map(|comma_separated| comma_separated.split(','))
                     .collect::<Vec<_>>()
             } else {
                 Vec::new()
             };
-            ac_token = token.access_token().secret().to_owned();
-            debug!("Access Token: {:?}", ac_token);
+            debug!("Access Token: {:?}", token.access_token().secret());
             debug!("42API returned the following scopes:\n{:?}\n", scopes);
+            ac_token = token;
             break;
         }
     }
     Ok(ac_token)
 }
 
+/// Reads the code and state from the redirect request line.
+fn parse_redirect(request_line: &str) -> Result<(AuthorizationCode, CsrfToken), SessionError> {
+    let redirect_url = match request_line.split_whitespace().nth(1) {
+        Some(url) => url,
+        None => return Err(SessionError::NoneError),
+    };
+    let url = Url::parse(&("http://localhost".to_string() + redirect_url))?;
+
+    let code_pair = match url.query_pairs().find(|pair| {
+        let (key, _) = pair;
+        key == "code"
+    }) {
+        Some(code) => code,
+        None => return Err(SessionError::NoneError),
+    };
+
+    let (_, value) = code_pair;
+    let code = AuthorizationCode::new(value.into_owned());
+
+    let state_pair = match url.query_pairs().find(|pair| {
+        let (key, _) = pair;
+        key == "state"
+    }) {
+        Some(state) => state,
+        None => return Err(SessionError::NoneError),
+    };
+
+    let (_, value) = state_pair;
+    let state = CsrfToken::new(value.into_owned());
+    Ok((code, state))
+}
+
+/// Rejects a redirect whose state was not issued by this authorization.
+fn verify_state(expected: &CsrfToken, received: &CsrfToken) -> Result<(), SessionError> {
+    if expected.secret() != received.secret() {
+        warn!("local_server(): state mismatch, possible CSRF attempt");
+        return Err(SessionError::CsrfMismatch);
+    }
+    Ok(())
+}
+
+#[test]
+fn parse_redirect_test() {
+    let (code, state) = parse_redirect("GET /?code=abc&state=xyz HTTP/1.1\r\n").unwrap();
+    assert_eq!(code.secret(), "abc");
+    assert_eq!(state.secret(), "xyz");
+    assert!(matches!(
+        parse_redirect("GET /?state=xyz HTTP/1.1\r\n"),
+        Err(SessionError::NoneError)
+    ));
+}
+
+#[test]
+fn verify_state_test() {
+    let expected = CsrfToken::new("xyz".to_string());
+    assert!(verify_state(&expected, &CsrfToken::new("xyz".to_string())).is_ok());
+    assert!(matches!(
+        verify_state(&expected, &CsrfToken::new("forged".to_string())),
+        Err(SessionError::CsrfMismatch)
+    ));
+}
+
 /*
 #[tokio::test]
 async fn authorize_test() {
@@ -215,3 +473,36 @@ async fn authorize_test() {
     }
 }
 */
+
+#[tokio::test]
+async fn generate_token_unknown_scope_test() {
+    let mut session = Session::default();
+    session.set_scopes(vec!["public".to_string(), "everything".to_string()]);
+    let res = generate_token(session).await;
+    assert!(matches!(res, Err(SessionError::UnknownScope(scope)) if scope == "everything"));
+}
+
+#[tokio::test]
+async fn generate_token_non_interactive_test() {
+    let mut session = Session::default();
+    session.set_interactive(false);
+    match generate_token(session).await {
+        Err(SessionError::AuthenticationRequired { url }) => {
+            assert!(url.starts_with("https://api.intra.42.fr/oauth/authorize?"));
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn generate_token_authorize_callback_test() {
+    let seen = Arc::new(std::sync::Mutex::new(None));
+    let mut session = Session::default();
+    let sink = Arc::clone(&seen);
+    session.set_authorize_callback(move |url: &Url| *sink.lock().unwrap() = Some(url.clone()));
+    session.set_bind_address("127.0.0.1:0".to_string());
+    session.set_login_timeout(std::time::Duration::from_millis(10));
+    let res = generate_token(session).await;
+    assert!(matches!(res, Err(SessionError::LoginTimeout(_))));
+    assert!(seen.lock().unwrap().is_some());
+}